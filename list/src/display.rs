@@ -0,0 +1,15 @@
+use std::fmt::{self, Display, Formatter};
+
+// Shared `Display` output of the lists: `1 -> 2 -> 3 -> ∅`.
+// Flags like width and precision apply to every element
+pub(crate) fn arrows<T: Display>(
+    items: impl Iterator<Item = T>,
+    f: &mut Formatter<'_>,
+) -> fmt::Result {
+    for item in items {
+        item.fmt(f)?;
+        f.write_str(" -> ")?;
+    }
+
+    f.write_str("∅")
+}
@@ -0,0 +1,125 @@
+use std::fmt::{Debug, Write};
+
+// Builds the text of a Graphviz graph for the `to_dot` methods of the lists.
+// Nodes are boxes laid out left to right, labelled with the `Debug` output of their element.
+// Pointers into the list (`head`, `tail`, list versions) are drawn as plain text with
+// an arrow to the node they point at.
+//
+// Render the output with `dot -Tsvg list.dot -o list.svg`
+pub(crate) struct Dot {
+    out: String,
+    pointers: usize,
+}
+
+impl Dot {
+    pub(crate) fn new() -> Self {
+        Dot {
+            out: String::from("digraph list {\n    rankdir=LR;\n    node [shape=box];\n"),
+            pointers: 0,
+        }
+    }
+
+    pub(crate) fn node<T: Debug>(&mut self, id: usize, data: &T) {
+        let label = escape(&format!("{data:?}"));
+        writeln!(self.out, "    n{id} [label=\"{label}\"];").unwrap();
+    }
+
+    pub(crate) fn edge(&mut self, from: usize, to: usize) {
+        writeln!(self.out, "    n{from} -> n{to};").unwrap();
+    }
+
+    pub(crate) fn dashed_edge(&mut self, from: usize, to: usize) {
+        writeln!(self.out, "    n{from} -> n{to} [style=dashed];").unwrap();
+    }
+
+    /// Draws `items` as a chain of nodes `n0 -> n1 -> ...`, with a `head` pointer to the first
+    /// node and, if `tail` is set, a `tail` pointer to the last one. Returns the number of nodes
+    pub(crate) fn chain<'a, T: Debug + 'a>(
+        &mut self,
+        items: impl Iterator<Item = &'a T>,
+        tail: bool,
+    ) -> usize {
+        let mut len: usize = 0;
+        for (i, data) in items.enumerate() {
+            self.node(i, data);
+            if i > 0 {
+                self.edge(i - 1, i);
+            }
+            len += 1;
+        }
+
+        let last = len.checked_sub(1);
+        self.pointer("head", last.map(|_| 0));
+        if tail {
+            self.pointer("tail", last);
+        }
+        len
+    }
+
+    /// Adds a named pointer. A pointer to nothing (an empty list) is drawn without an arrow
+    pub(crate) fn pointer(&mut self, name: &str, to: Option<usize>) {
+        let id = self.pointers;
+        self.pointers += 1;
+
+        let name = escape(name);
+        writeln!(self.out, "    p{id} [label=\"{name}\", shape=plaintext];").unwrap();
+        if let Some(to) = to {
+            writeln!(self.out, "    p{id} -> n{to};").unwrap();
+        }
+    }
+
+    pub(crate) fn finish(mut self) -> String {
+        self.out.push_str("}\n");
+        self.out
+    }
+}
+
+fn escape(label: &str) -> String {
+    label.replace('\\', "\\\\").replace('"', "\\\"")
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn graph() {
+        let mut dot = Dot::new();
+        dot.node(0, &"a");
+        dot.node(1, &'"');
+        dot.edge(0, 1);
+        dot.pointer("head", Some(0));
+        dot.pointer("empty", None);
+
+        assert_eq!(
+            dot.finish(),
+            r#"digraph list {
+    rankdir=LR;
+    node [shape=box];
+    n0 [label="\"a\""];
+    n1 [label="'\"'"];
+    n0 -> n1;
+    p0 [label="head", shape=plaintext];
+    p0 -> n0;
+    p1 [label="empty", shape=plaintext];
+}
+"#
+        );
+    }
+
+    #[test]
+    fn chain() {
+        let mut dot = Dot::new();
+        assert_eq!(dot.chain([1, 2].iter(), true), 2);
+        let out = dot.finish();
+        assert!(out.contains("n0 -> n1;"));
+        assert!(out.contains("p0 -> n0;"));
+        assert!(out.contains("p1 [label=\"tail\", shape=plaintext];\n    p1 -> n1;"));
+
+        let mut dot = Dot::new();
+        assert_eq!(dot.chain(std::iter::empty::<&i32>(), false), 0);
+        let out = dot.finish();
+        assert!(out.contains("p0 [label=\"head\""));
+        assert!(!out.contains("->") && !out.contains("tail"));
+    }
+}
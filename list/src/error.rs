@@ -0,0 +1,40 @@
+use std::fmt::{self, Display, Formatter};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ListError {
+    /// Index is out of bounds for a list of length `len`
+    IndexOutOfBounds { index: usize, len: usize },
+    /// Operation needs an element, but the list is empty
+    Empty,
+}
+
+impl Display for ListError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            ListError::IndexOutOfBounds { index, len } => {
+                write!(f, "index {index} is out of bounds for list of length {len}")
+            }
+            ListError::Empty => write!(f, "list is empty"),
+        }
+    }
+}
+
+impl std::error::Error for ListError {}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn display() {
+        let error = ListError::IndexOutOfBounds { index: 5, len: 2 };
+        assert_eq!(
+            error.to_string(),
+            "index 5 is out of bounds for list of length 2"
+        );
+        assert_eq!(ListError::Empty.to_string(), "list is empty");
+
+        let boxed: Box<dyn std::error::Error> = Box::new(error);
+        assert!(boxed.source().is_none());
+    }
+}
@@ -1,3 +1,14 @@
+mod display;
+mod dot;
+pub mod error;
 pub mod linked_list_1;
 pub mod linked_list_2;
 pub mod linked_list_3;
+pub mod linked_list_4;
+pub mod persistent_queue;
+pub mod sequence;
+#[cfg(feature = "serde")]
+mod serde_impls;
+pub mod zipper;
+
+pub use error::ListError;
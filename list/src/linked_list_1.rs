@@ -1,24 +1,28 @@
 use std::{
     fmt::{self, Display, Formatter},
-    mem,
+    iter::FusedIterator,
+    mem::{self, ManuallyDrop},
+    ptr,
 };
 
+use crate::{display, dot::Dot, sequence::Sequence};
+
 #[derive(PartialEq, Eq, Default)]
-pub enum LinkedList {
+pub enum LinkedList<T> {
     #[default]
     Empty,
-    Elem(usize, Box<LinkedList>),
+    Elem(T, Box<LinkedList<T>>),
 }
 
-impl LinkedList {
+impl<T> LinkedList<T> {
     pub fn new() -> Self {
         LinkedList::Empty
     }
 
-    pub fn val(&self) -> Option<usize> {
+    pub fn val(&self) -> Option<&T> {
         match self {
             LinkedList::Empty => None,
-            LinkedList::Elem(val, _) => Some(*val),
+            LinkedList::Elem(val, _) => Some(val),
         }
     }
 
@@ -37,13 +41,13 @@ impl LinkedList {
         matches!(self, LinkedList::Empty)
     }
 
-    pub fn push_front(&mut self, item: usize) {
+    pub fn push_front(&mut self, item: T) {
         let mut old_head = LinkedList::new();
         std::mem::swap(&mut old_head, self);
         *self = LinkedList::Elem(item, Box::new(old_head));
     }
 
-    pub fn push_back(&mut self, item: usize) {
+    pub fn push_back(&mut self, item: T) {
         let tail = LinkedList::Elem(item, Box::new(LinkedList::Empty));
         if self.is_empty() {
             *self = tail;
@@ -52,35 +56,31 @@ impl LinkedList {
 
         let mut head = self;
         while let LinkedList::Elem(_, next) = head {
-            if **next == LinkedList::Empty {
-                *next = Box::new(tail);
+            if next.is_empty() {
+                **next = tail;
                 return;
             }
             head = next;
         }
     }
 
-    pub fn pop_back(&mut self) -> Option<usize> {
+    pub fn pop_back(&mut self) -> Option<T> {
         if self.is_empty() {
             return None;
         }
 
         // Special case: only one element in the list
         if let LinkedList::Elem(_, next) = self {
-            if **next == LinkedList::Empty {
-                let mut tail = LinkedList::Empty;
-                mem::swap(self, &mut tail);
-                return tail.val();
+            if next.is_empty() {
+                return mem::take(self).into_parts().map(|(val, _)| val);
             }
         }
 
         let mut head = self;
         while let LinkedList::Elem(_, next) = head {
             if let LinkedList::Elem(_, next_next) = &**next {
-                if **next_next == LinkedList::Empty {
-                    let mut tail = LinkedList::Empty;
-                    mem::swap(&mut tail, next);
-                    return tail.val();
+                if next_next.is_empty() {
+                    return mem::take(&mut **next).into_parts().map(|(val, _)| val);
                 }
             }
             head = next;
@@ -89,17 +89,16 @@ impl LinkedList {
         None
     }
 
-    pub fn pop_front(&mut self) -> Option<usize> {
-        match std::mem::replace(self, LinkedList::Empty) {
-            LinkedList::Empty => None,
-            LinkedList::Elem(val, next) => {
-                *self = *next;
-                Some(val)
-            }
-        }
+    pub fn pop_front(&mut self) -> Option<T> {
+        let (val, next) = mem::take(self).into_parts()?;
+        *self = *next;
+        Some(val)
     }
 
-    pub fn find(&self, item: usize) -> Option<usize> {
+    pub fn find(&self, item: T) -> Option<usize>
+    where
+        T: PartialEq,
+    {
         let mut idx = 0;
         let mut head = self;
 
@@ -114,49 +113,145 @@ impl LinkedList {
         None
     }
 
-    pub fn iter(&self) -> LinkedListIter {
+    pub fn iter(&self) -> LinkedListIter<'_, T> {
         LinkedListIter { head: self }
     }
+
+    // `LinkedList` implements `Drop`, so we can't move its fields out by pattern matching
+    fn into_parts(self) -> Option<(T, Box<LinkedList<T>>)> {
+        let this = ManuallyDrop::new(self);
+        match &*this {
+            LinkedList::Empty => None,
+            // SAFETY: `this` is never dropped, so each field is read out of it exactly once
+            LinkedList::Elem(val, next) => unsafe { Some((ptr::read(val), ptr::read(next))) },
+        }
+    }
 }
 
-impl fmt::Debug for LinkedList {
-    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
-        for item in self.iter() {
-            writeln!(f, "{:?} ", item)?;
+// Default drop would recurse once per element through `Box<LinkedList>`, which overflows
+// the stack on long lists. Instead we unlink one element at a time
+impl<T> Drop for LinkedList<T> {
+    fn drop(&mut self) {
+        let LinkedList::Elem(_, next) = self else {
+            return;
+        };
+
+        let mut current = mem::take(&mut **next);
+        while let LinkedList::Elem(_, next) = &mut current {
+            current = mem::take(&mut **next);
         }
+    }
+}
 
-        Ok(())
+impl<T: fmt::Debug> LinkedList<T> {
+    /// Graphviz source of the list, see `dot.rs`
+    pub fn to_dot(&self) -> String {
+        let mut dot = Dot::new();
+        dot.chain(self.iter(), false);
+        dot.finish()
     }
 }
 
-impl Display for LinkedList {
+impl<T: fmt::Debug> fmt::Debug for LinkedList<T> {
     fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
-        for item in self.iter() {
-            writeln!(f, "{} ", item)?;
-        }
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
 
-        Ok(())
+impl<T: Display> Display for LinkedList<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        display::arrows(self.iter(), f)
     }
 }
 
-pub struct LinkedListIter<'a> {
-    head: &'a LinkedList,
+impl<T> Sequence<T> for LinkedList<T> {
+    type Iter<'a>
+        = LinkedListIter<'a, T>
+    where
+        T: 'a;
+
+    fn len(&self) -> usize {
+        LinkedList::len(self)
+    }
+
+    fn push_front(&mut self, data: T) {
+        LinkedList::push_front(self, data)
+    }
+
+    fn push_back(&mut self, data: T) {
+        LinkedList::push_back(self, data)
+    }
+
+    fn pop_front(&mut self) -> Option<T> {
+        LinkedList::pop_front(self)
+    }
+
+    fn pop_back(&mut self) -> Option<T> {
+        LinkedList::pop_back(self)
+    }
+
+    fn iter(&self) -> LinkedListIter<'_, T> {
+        LinkedList::iter(self)
+    }
+
+    fn is_empty(&self) -> bool {
+        LinkedList::is_empty(self)
+    }
+
+    fn front(&self) -> Option<&T> {
+        self.val()
+    }
+}
+
+pub struct LinkedListIter<'a, T> {
+    head: &'a LinkedList<T>,
 }
 
-impl<'a> Iterator for LinkedListIter<'a> {
-    type Item = usize;
+impl<'a, T> Iterator for LinkedListIter<'a, T> {
+    type Item = &'a T;
 
     fn next(&mut self) -> Option<Self::Item> {
         match self.head {
             LinkedList::Empty => None,
             LinkedList::Elem(val, next) => {
                 self.head = next;
-                Some(*val)
+                Some(val)
             }
         }
     }
+
+    // the list doesn't store its length, so the remaining part is counted on demand
+    // instead of making every `iter()` call walk the whole list up front
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.head.len();
+        (len, Some(len))
+    }
+
+    fn nth(&mut self, n: usize) -> Option<Self::Item> {
+        for _ in 0..n {
+            match self.head {
+                LinkedList::Empty => return None,
+                LinkedList::Elem(_, next) => self.head = next,
+            }
+        }
+        self.next()
+    }
+
+    fn last(self) -> Option<Self::Item> {
+        let mut last = None;
+        let mut current = self.head;
+        while let LinkedList::Elem(val, next) = current {
+            last = Some(val);
+            current = next;
+        }
+        last
+    }
 }
 
+impl<T> ExactSizeIterator for LinkedListIter<'_, T> {}
+
+impl<T> FusedIterator for LinkedListIter<'_, T> {}
+
 #[cfg(test)]
 mod test {
     use crate::linked_list_1::LinkedList;
@@ -268,4 +363,105 @@ mod test {
         assert_eq!(list.pop_back(), None);
         assert_eq!(list.len(), 0);
     }
+
+    #[test]
+    fn drop_long_list() {
+        let mut list = LinkedList::new();
+        for i in 0..2_000_000 {
+            list.push_front(i);
+        }
+        drop(list);
+    }
+
+    #[test]
+    fn strings() {
+        let mut list = LinkedList::new();
+        list.push_back(String::from("b"));
+        list.push_back(String::from("c"));
+        list.push_front(String::from("a"));
+
+        assert_eq!(list.val(), Some(&String::from("a")));
+        assert_eq!(list.find(String::from("c")), Some(2));
+        assert!(list.iter().eq(["a", "b", "c"].iter()));
+
+        assert_eq!(list.pop_back(), Some(String::from("c")));
+        assert_eq!(list.pop_front(), Some(String::from("a")));
+        assert_eq!(list.pop_back(), Some(String::from("b")));
+        assert_eq!(list.pop_back(), None);
+    }
+
+    #[test]
+    fn iter_len() {
+        let mut list = LinkedList::new();
+        list.push_back(1);
+        list.push_back(2);
+        list.push_back(3);
+        let mut iter = list.iter();
+        assert_eq!(iter.len(), 3);
+        iter.next();
+        assert_eq!(iter.size_hint(), (2, Some(2)));
+        assert_eq!(iter.by_ref().count(), 2);
+        assert_eq!(iter.next(), None);
+    }
+
+    #[test]
+    fn iter_nth_and_last() {
+        let mut list = LinkedList::new();
+        for i in 1..=5 {
+            list.push_back(i);
+        }
+
+        let mut iter = list.iter();
+        assert_eq!(iter.nth(1), Some(&2));
+        assert_eq!(iter.len(), 3);
+        assert_eq!(iter.nth(5), None);
+        assert_eq!(iter.len(), 0);
+        assert_eq!(iter.next(), None);
+        assert_eq!(list.iter().last(), Some(&5));
+        assert_eq!(list.iter().skip(5).last(), None);
+    }
+
+    #[test]
+    fn to_dot() {
+        let mut list = LinkedList::new();
+        assert!(!list.to_dot().contains("->"));
+
+        list.push_back("a");
+        list.push_back("b");
+        let dot = list.to_dot();
+        assert!(dot.contains(r#"n1 [label="\"b\""];"#));
+        assert!(dot.contains("p0 -> n0;"));
+        assert!(dot.contains("n0 -> n1;"));
+    }
+
+    #[test]
+    fn debug_and_display() {
+        let mut list = LinkedList::new();
+        assert_eq!(format!("{:?}", list), "[]");
+        assert_eq!(list.to_string(), "∅");
+
+        list.push_back(1.5);
+        list.push_back(2.25);
+        assert_eq!(format!("{:?}", list), "[1.5, 2.25]");
+        assert_eq!(format!("{:#?}", list), "[\n    1.5,\n    2.25,\n]");
+        assert_eq!(list.to_string(), "1.5 -> 2.25 -> ∅");
+        assert_eq!(format!("{:6.2}", list), "  1.50 ->   2.25 -> ∅");
+    }
+
+    #[test]
+    fn records() {
+        #[derive(Debug, PartialEq)]
+        struct Point {
+            x: i32,
+            y: i32,
+        }
+
+        let mut list = LinkedList::new();
+        list.push_back(Point { x: 1, y: 2 });
+        list.push_back(Point { x: 2, y: 3 });
+        assert_eq!(list.find(Point { x: 2, y: 3 }), Some(1));
+        assert_eq!(list.iter().map(|p| p.x).sum::<i32>(), 3);
+        assert_eq!(list.pop_front(), Some(Point { x: 1, y: 2 }));
+        assert_eq!(list.len(), 1);
+    }
 }
@@ -1,18 +1,65 @@
-use anyhow::{bail, Result};
+use std::{
+    cmp::Ordering,
+    fmt,
+    hash::{Hash, Hasher},
+    iter::FusedIterator,
+    marker::PhantomData,
+    mem,
+    ops::{Bound, Index, IndexMut, RangeBounds},
+    ptr::{self, NonNull},
+};
+
+use crate::{display, dot::Dot, sequence::Sequence, ListError};
+
+/// Creates a `linked_list_2::LinkedList` containing the arguments, like `vec!`
+#[macro_export]
+macro_rules! list {
+    () => {
+        $crate::linked_list_2::LinkedList::new()
+    };
+    ($($x:expr),+ $(,)?) => {
+        $crate::linked_list_2::LinkedList::from([$($x),+])
+    };
+}
+
+type Link<T> = Option<NonNull<Node<T>>>;
 
-#[derive(Default)]
+// Every node is allocated with `Box::new` and owned by the list, but nodes are linked with
+// raw pointers. We keep a pointer to the last node, so that push_back and merge are O(1),
+// and that pointer aliases the `next` link of the node before it. `Box` links don't allow this:
+// moving or borrowing a `Box` asserts it's the only pointer to its node, which invalidates tail.
+// Run `cargo +nightly miri test -- --skip long` after changing any of the unsafe code below
 pub struct LinkedList<T> {
-    head: Option<Box<Node<T>>>,
+    head: Link<T>,
+    tail: Link<T>,
+    len: usize,
+    // tells the compiler we own `Node<T>` values, even though we only store pointers
+    _marker: PhantomData<Box<Node<T>>>,
 }
 
 struct Node<T> {
     data: T,
-    next: Option<Box<Node<T>>>,
+    next: Link<T>,
+}
+
+// SAFETY: the list owns its nodes exclusively, same as `Box<Node<T>>` would
+unsafe impl<T: Send> Send for LinkedList<T> {}
+unsafe impl<T: Sync> Sync for LinkedList<T> {}
+
+impl<T> Default for LinkedList<T> {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 impl<T> LinkedList<T> {
     pub fn new() -> Self {
-        LinkedList { head: None }
+        LinkedList {
+            head: None,
+            tail: None,
+            len: 0,
+            _marker: PhantomData,
+        }
     }
 
     pub fn is_empty(&self) -> bool {
@@ -20,61 +67,60 @@ impl<T> LinkedList<T> {
     }
 
     pub fn has_exactly_one_element(&self) -> bool {
-        self.head.as_ref().is_some_and(|n| n.next.is_none())
+        self.len == 1
     }
 
     pub fn len(&self) -> usize {
-        let mut count = 0;
-        for _ in self.iter() {
-            count += 1;
-        }
-        count
+        self.len
     }
 
     pub fn push_back(&mut self, data: T) {
-        let mut current = &mut self.head;
-        while let Some(node) = current {
-            current = &mut node.next;
+        let node = Node::new_raw(data, None);
+        match self.tail {
+            // SAFETY: tail points to a live node owned by this list
+            Some(tail) => unsafe { (*tail.as_ptr()).next = Some(node) },
+            None => self.head = Some(node),
         }
-        *current = Node::new_boxed(data, None)
+        self.tail = Some(node);
+        self.len += 1;
     }
 
     pub fn push_front(&mut self, data: T) {
+        let node = Node::new_raw(data, self.head);
         if self.is_empty() {
-            self.head = Node::new_boxed(data, None);
-            return;
+            self.tail = Some(node);
         }
-
-        self.head = Node::new_boxed(data, self.head.take());
+        self.head = Some(node);
+        self.len += 1;
     }
 
     pub fn pop_back(&mut self) -> Option<T> {
-        if self.is_empty() {
-            return None;
-        }
-
-        if self.has_exactly_one_element() {
-            return self.head.take().map(|node| node.data);
+        if self.len <= 1 {
+            return self.pop_front();
         }
 
-        let mut current = &mut self.head;
-        while let Some(ref mut node) = *current {
-            //Note: in next.as_ref()?, '?' is OK, because we've handle special case of 1 element
-            //above.In this loop we look forward 2 elements. In case next_next is empty (None)
-            //we are done. This is why  we know next will always exist
-            if node.next.as_ref()?.next.is_none() {
-                return node.next.take().map(|node| node.data);
-            }
-            current = &mut node.next;
+        // list is singly linked, so we still have to walk to the node before the last one
+        let new_tail = self.node_at(self.len - 2)?;
+        // SAFETY: new_tail is a live node, and the node after it is the old tail, which we
+        // unlink right now so nothing else will reach it afterwards
+        unsafe {
+            let old_tail = (*new_tail.as_ptr()).next.take()?;
+            self.tail = Some(new_tail);
+            self.len -= 1;
+            Some(Box::from_raw(old_tail.as_ptr()).data)
         }
-
-        //unreachable because we've handle special case of 0 elements before loop
-        unreachable!()
     }
 
     pub fn pop_front(&mut self) -> Option<T> {
-        self.head.take().map(|node| {
+        self.head.map(|head| {
+            // SAFETY: head was created by `Node::new_raw` and we are unlinking it right now,
+            // so nothing else will reach it afterwards
+            let node = unsafe { Box::from_raw(head.as_ptr()) };
             self.head = node.next;
+            if self.head.is_none() {
+                self.tail = None;
+            }
+            self.len -= 1;
             node.data
         })
     }
@@ -83,18 +129,85 @@ impl<T> LinkedList<T> {
     where
         T: PartialEq,
     {
-        let mut current = &self.head;
-        let mut index = 0;
+        self.iter().position(|d| *d == data)
+    }
 
-        while let Some(node) = current {
-            if node.data == data {
-                return Some(index);
-            }
+    /// Returns index of the last element equal to data
+    pub fn last_index_of(&self, data: &T) -> Option<usize>
+    where
+        T: PartialEq,
+    {
+        self.rposition(|d| d == data)
+    }
 
-            index += 1;
-            current = &node.next;
-        }
+    /// Returns indexes of all elements equal to data, in ascending order
+    pub fn all_indices_of(&self, data: &T) -> Vec<usize>
+    where
+        T: PartialEq,
+    {
+        self.iter()
+            .enumerate()
+            .filter(|(_, d)| *d == data)
+            .map(|(index, _)| index)
+            .collect()
+    }
+
+    pub fn contains(&self, data: &T) -> bool
+    where
+        T: PartialEq,
+    {
+        self.iter().any(|d| d == data)
+    }
+
+    /// Returns index of the first element for which predicate returns true
+    pub fn position<P>(&self, predicate: P) -> Option<usize>
+    where
+        P: FnMut(&T) -> bool,
+    {
+        self.iter().position(predicate)
+    }
+
+    /// Returns index of the last element for which predicate returns true.
+    /// List is singly linked, so this always walks the whole list
+    pub fn rposition<P>(&self, mut predicate: P) -> Option<usize>
+    where
+        P: FnMut(&T) -> bool,
+    {
+        self.iter()
+            .enumerate()
+            .filter(|(_, d)| predicate(d))
+            .last()
+            .map(|(index, _)| index)
+    }
+
+    /// Returns the first element for which predicate returns true
+    pub fn find<P>(&self, mut predicate: P) -> Option<&T>
+    where
+        P: FnMut(&T) -> bool,
+    {
+        self.iter().find(|d| predicate(d))
+    }
+
+    /// Returns the first element for which predicate returns true
+    pub fn find_mut<P>(&mut self, mut predicate: P) -> Option<&mut T>
+    where
+        P: FnMut(&T) -> bool,
+    {
+        self.iter_mut().find(|d| predicate(d))
+    }
 
+    /// Removes the first element for which predicate returns true and returns it
+    pub fn remove_first<P>(&mut self, mut predicate: P) -> Option<T>
+    where
+        P: FnMut(&T) -> bool,
+    {
+        let mut cursor = self.cursor_front_mut();
+        while let Some(data) = cursor.current() {
+            if predicate(data) {
+                return cursor.remove_current();
+            }
+            cursor.move_next();
+        }
         None
     }
 
@@ -108,94 +221,560 @@ impl<T> LinkedList<T> {
             return;
         }
 
-        let mut current = &mut self.head;
-        for _ in 0..index - 1 {
-            match current {
-                Some(node) => current = &mut node.next,
-                // as soon as we go out of bounds, we push_back and we're done
-                None => {
-                    self.push_back(data);
-                    return;
-                }
+        if index >= self.len {
+            self.push_back(data);
+            return;
+        }
+
+        let prev = self
+            .node_at(index - 1)
+            .expect("index < len, so node exists");
+        // SAFETY: prev is a live node owned by this list
+        unsafe {
+            let node = Node::new_raw(data, (*prev.as_ptr()).next);
+            (*prev.as_ptr()).next = Some(node);
+        }
+        self.len += 1;
+    }
+
+    /// Same as `insert_at`, but returns an error instead of pushing to the back
+    /// when index > list.len(). Inserting at index == list.len() pushes to the back
+    pub fn try_insert_at(&mut self, index: usize, data: T) -> Result<(), ListError> {
+        if index > self.len {
+            return Err(ListError::IndexOutOfBounds {
+                index,
+                len: self.len,
+            });
+        }
+
+        self.insert_at(index, data);
+        Ok(())
+    }
+
+    /// Returns element at index, which is 0 based
+    pub fn try_get(&self, index: usize) -> Result<&T, ListError> {
+        if self.is_empty() {
+            return Err(ListError::Empty);
+        }
+
+        // SAFETY: node lives as long as the list, which is borrowed for the returned lifetime
+        unsafe { Node::as_ref(self.node_at(index)) }
+            .map(|n| &n.data)
+            .ok_or(ListError::IndexOutOfBounds {
+                index,
+                len: self.len,
+            })
+    }
+
+    /// Removes element at index, which is 0 based, and returns it
+    pub fn try_remove_at(&mut self, index: usize) -> Result<T, ListError> {
+        if self.is_empty() {
+            return Err(ListError::Empty);
+        }
+
+        if index >= self.len {
+            return Err(ListError::IndexOutOfBounds {
+                index,
+                len: self.len,
+            });
+        }
+
+        if index == 0 {
+            return Ok(self.pop_front().expect("list is not empty"));
+        }
+
+        let prev = self
+            .node_at(index - 1)
+            .expect("index < len, so node exists");
+        // SAFETY: prev and the node after it are live nodes; we unlink the latter right now,
+        // so nothing else will reach it afterwards
+        unsafe {
+            let removed = (*prev.as_ptr()).next.expect("index < len, so node exists");
+            let node = Box::from_raw(removed.as_ptr());
+            (*prev.as_ptr()).next = node.next;
+            if self.tail == Some(removed) {
+                self.tail = Some(prev);
             }
+            self.len -= 1;
+            Ok(node.data)
         }
+    }
+
+    /// Returns element at index, which is 0 based, or None if index >= list.len()
+    pub fn get(&self, index: usize) -> Option<&T> {
+        self.try_get(index).ok()
+    }
+
+    /// Returns element at index, which is 0 based, or None if index >= list.len()
+    pub fn get_mut(&mut self, index: usize) -> Option<&mut T> {
+        // SAFETY: node is a live node owned by this list, which is mutably borrowed
+        // for the returned lifetime
+        self.node_at(index)
+            .map(|n| unsafe { &mut (*n.as_ptr()).data })
+    }
+
+    /// Removes element at index, which is 0 based, and returns it.
+    /// Returns None if index >= list.len()
+    pub fn remove_at(&mut self, index: usize) -> Option<T> {
+        self.try_remove_at(index).ok()
+    }
 
-        let current = current.as_mut().expect("None case already handled");
-        let new_node = Node::new_boxed(data, current.next.take());
-        current.next = new_node;
+    /// Replaces element at index, which is 0 based, and returns the old one.
+    /// If index >= list.len(), list stays unchanged, data is dropped and None is returned
+    pub fn replace_at(&mut self, index: usize, data: T) -> Option<T> {
+        self.get_mut(index).map(|old| mem::replace(old, data))
     }
 
-    pub fn split_at(mut self, index: usize) -> Result<(LinkedList<T>, LinkedList<T>)> {
-        if index >= self.len() {
-            bail!("Index out of bounds");
+    /// Swaps elements at indexes i and j, walking the list only once.
+    /// Panics if i or j >= list.len(), same as indexing does
+    pub fn swap(&mut self, i: usize, j: usize) {
+        for index in [i, j] {
+            if index >= self.len {
+                self.index_out_of_bounds(index);
+            }
+        }
+
+        if i == j {
+            return;
+        }
+
+        let (low, high) = (i.min(j), i.max(j));
+        let low_node = self.node_at(low).expect("index < len, so node exists");
+        let mut high_node = low_node;
+        for _ in low..high {
+            // SAFETY: we only follow links between live nodes owned by this list
+            high_node = unsafe { (*high_node.as_ptr()).next }.expect("index < len, so node exists");
+        }
+
+        // SAFETY: both are live nodes owned by this list, and they're different nodes since
+        // low != high, so the two data fields don't overlap
+        unsafe {
+            ptr::swap(
+                &mut (*low_node.as_ptr()).data,
+                &mut (*high_node.as_ptr()).data,
+            )
         }
+    }
 
+    /// Splits list so that first list contains elements 0..=index and second one the rest.
+    /// Returns an error if list is empty or index >= list.len()
+    /// NOTE: element at index goes to the first list, so splitting at the last index
+    /// returns an empty second list. Kept for compatibility, `split_off` follows std semantics
+    pub fn split_at(mut self, index: usize) -> Result<(LinkedList<T>, LinkedList<T>), ListError> {
         // edge cases
         if self.is_empty() {
-            return Ok((Self::new(), Self::new()));
+            return Err(ListError::Empty);
         }
 
-        if self.has_exactly_one_element() {
-            return Ok((self, Self::new()));
+        if index >= self.len {
+            return Err(ListError::IndexOutOfBounds {
+                index,
+                len: self.len,
+            });
         }
 
-        let split_at_last_element = index == self.len() - 1; // because we have 0 based index
-        if split_at_last_element {
-            return Ok((self, Self::new()));
+        let split_node = self.node_at(index).expect("index < len, so node exists");
+        let mut second_list = Self::new();
+        // SAFETY: split_node is a live node; nodes after it now belong to second_list
+        second_list.head = unsafe { (*split_node.as_ptr()).next.take() };
+        if second_list.head.is_some() {
+            second_list.tail = self.tail;
+            second_list.len = self.len - index - 1;
         }
 
-        let mut current = &mut self.head;
-        for _ in 0..index {
-            if let Some(node) = current {
-                current = &mut node.next;
-            }
+        self.tail = Some(split_node);
+        self.len = index + 1;
+
+        Ok((self, second_list))
+    }
+
+    /// Splits the list in two at the given index, same as `std::collections::LinkedList`.
+    /// Self keeps elements [0, at) and the returned list gets [at, len).
+    /// Panics if at > list.len()
+    pub fn split_off(&mut self, at: usize) -> LinkedList<T> {
+        assert!(
+            at <= self.len,
+            "cannot split off at index {at}, list length is {}",
+            self.len
+        );
+        if at == 0 {
+            return mem::take(self);
+        }
+
+        let mut cursor = self.cursor_front_mut();
+        for _ in 0..at - 1 {
+            cursor.move_next();
         }
+        cursor.split_after()
+    }
 
-        let split_node = current.as_mut().expect(
-            "Current here must exist, as case of splitting at last element was already handled",
+    /// Removes elements in range from the list and returns them as an iterator.
+    /// Same as `Vec::drain`, the whole range is removed even if the iterator isn't consumed.
+    /// Panics if range start > range end or range end > list.len()
+    pub fn drain<R>(&mut self, range: R) -> Drain<'_, T>
+    where
+        R: RangeBounds<usize>,
+    {
+        let start = match range.start_bound() {
+            Bound::Included(&start) => start,
+            Bound::Excluded(&start) => start.checked_add(1).expect("range start overflow"),
+            Bound::Unbounded => 0,
+        };
+        let end = match range.end_bound() {
+            Bound::Included(&end) => end.checked_add(1).expect("range end overflow"),
+            Bound::Excluded(&end) => end,
+            Bound::Unbounded => self.len,
+        };
+        assert!(
+            start <= end,
+            "drain range start {start} is greater than end {end}"
         );
-        let second_list = Self {
-            head: split_node.next.take(),
+        assert!(
+            end <= self.len,
+            "drain range end {end} is out of bounds for list of length {}",
+            self.len
+        );
+
+        let mut removed = self.split_off(start);
+        let rest = removed.split_off(end - start);
+        self.merge(rest);
+
+        Drain {
+            removed,
+            _marker: PhantomData,
+        }
+    }
+
+    pub fn merge(&mut self, mut other: Self) {
+        let Some(other_head) = other.head.take() else {
+            return;
         };
 
-        Ok((self, second_list))
+        match self.tail {
+            // SAFETY: tail is a live node; other's nodes now belong to self
+            Some(tail) => unsafe { (*tail.as_ptr()).next = Some(other_head) },
+            None => self.head = Some(other_head),
+        }
+
+        self.tail = other.tail.take();
+        self.len += mem::take(&mut other.len);
+    }
+
+    /// Inserts all elements of other at index, which is 0 based, without copying them,
+    /// so that the first element of other ends up at index.
+    /// Same as `insert_at`, if index >= list.len(), other is appended at the end of the list
+    pub fn splice_at(&mut self, index: usize, mut other: Self) {
+        if index == 0 {
+            other.merge(mem::take(self));
+            *self = other;
+            return;
+        }
+
+        if index >= self.len {
+            self.merge(other);
+            return;
+        }
+
+        let mut cursor = self.cursor_front_mut();
+        for _ in 0..index - 1 {
+            cursor.move_next();
+        }
+        cursor.splice_after(other);
+    }
+
+    /// Reverses the list in place, by flipping links between nodes
+    pub fn reverse(&mut self) {
+        let mut prev: Link<T> = None;
+        let mut current = self.head;
+        while let Some(node) = current {
+            // SAFETY: node is a live node owned by this list
+            unsafe {
+                current = (*node.as_ptr()).next;
+                (*node.as_ptr()).next = prev;
+            }
+            prev = Some(node);
+        }
+
+        self.tail = self.head;
+        self.head = prev;
+    }
+
+    /// Rotates the list so that the element at index k becomes the first one.
+    /// Walks k elements, the rest is relinked in O(1).
+    /// Panics if k > list.len(), same as `VecDeque::rotate_left`
+    pub fn rotate_left(&mut self, k: usize) {
+        assert!(
+            k <= self.len,
+            "cannot rotate list of length {} by {k}",
+            self.len
+        );
+        if k == 0 || k == self.len {
+            return;
+        }
+
+        let (first, mut second) = mem::take(self)
+            .split_at(k - 1)
+            .expect("0 < k < len, so index is in bounds");
+        second.merge(first);
+        *self = second;
+    }
+
+    /// Rotates the list so that the last k elements become the first ones.
+    /// Panics if k > list.len(), same as `VecDeque::rotate_right`
+    pub fn rotate_right(&mut self, k: usize) {
+        assert!(
+            k <= self.len,
+            "cannot rotate list of length {} by {k}",
+            self.len
+        );
+        self.rotate_left(self.len - k);
+    }
+
+    /// Merges other into self, when both are already sorted according to compare,
+    /// so that the result is sorted too. Runs in O(n + m) and only relinks existing nodes.
+    /// On equal elements, elements of self go first
+    pub fn merge_sorted<F>(&mut self, mut other: Self, mut compare: F)
+    where
+        F: FnMut(&T, &T) -> Ordering,
+    {
+        // other's nodes belong to self from here on, even if compare panics
+        let left = self.head.take();
+        let right = other.head.take();
+        self.tail = None;
+        self.len += mem::take(&mut other.len);
+        other.tail = None;
+
+        MergeGuard {
+            list: self,
+            left,
+            right,
+            rest: None,
+        }
+        .merge(&mut compare);
+    }
+
+    /// Keeps only elements for which f returns true, in a single pass and without allocating
+    pub fn retain<F>(&mut self, mut f: F)
+    where
+        F: FnMut(&T) -> bool,
+    {
+        self.retain_mut(|data| f(data));
+    }
+
+    /// Same as `retain`, but f can also modify elements
+    pub fn retain_mut<F>(&mut self, mut f: F)
+    where
+        F: FnMut(&mut T) -> bool,
+    {
+        let mut cursor = self.cursor_front_mut();
+        while let Some(data) = cursor.current() {
+            if f(data) {
+                cursor.move_next();
+            } else {
+                cursor.remove_current();
+            }
+        }
+    }
+
+    /// Returns an iterator that removes and yields elements for which predicate returns true.
+    /// Elements are unlinked lazily, so if the iterator isn't fully consumed,
+    /// the rest of the list stays as it is
+    pub fn extract_if<F>(&mut self, predicate: F) -> ExtractIf<'_, T, F>
+    where
+        F: FnMut(&mut T) -> bool,
+    {
+        ExtractIf {
+            cursor: self.cursor_front_mut(),
+            predicate,
+        }
+    }
+
+    /// Removes consecutive equal elements, keeping the first one of every run
+    pub fn dedup(&mut self)
+    where
+        T: PartialEq,
+    {
+        self.dedup_by(|a, b| a == b);
+    }
+
+    /// Removes consecutive elements that map to the same key
+    pub fn dedup_by_key<K, F>(&mut self, mut key: F)
+    where
+        K: PartialEq,
+        F: FnMut(&mut T) -> K,
+    {
+        self.dedup_by(|a, b| key(a) == key(b));
     }
 
-    pub fn merge(&mut self, other: Self) {
-        match (&self.head, &other.head) {
-            (None, None) => return,
-            (Some(_), None) => return,
-            (None, Some(_)) => {
-                self.head = other.head;
-                return;
+    /// Removes consecutive elements for which same_bucket returns true. Same as `Vec::dedup_by`,
+    /// same_bucket gets the element that is checked first and the previous kept element second
+    pub fn dedup_by<F>(&mut self, mut same_bucket: F)
+    where
+        F: FnMut(&mut T, &mut T) -> bool,
+    {
+        let Some(mut kept) = self.head else {
+            return;
+        };
+
+        // SAFETY: kept and next are live, distinct nodes owned by this list. We unlink next
+        // and fix tail before dropping it, so the list stays valid even if drop panics
+        unsafe {
+            while let Some(next) = (*kept.as_ptr()).next {
+                if same_bucket(&mut (*next.as_ptr()).data, &mut (*kept.as_ptr()).data) {
+                    let node = Box::from_raw(next.as_ptr());
+                    (*kept.as_ptr()).next = node.next;
+                    if self.tail == Some(next) {
+                        self.tail = Some(kept);
+                    }
+                    self.len -= 1;
+                    drop(node);
+                } else {
+                    kept = next;
+                }
             }
-            (_, _) => {}
         }
+    }
+
+    /// Sorts the list in ascending order. Sort is stable and doesn't allocate,
+    /// it only relinks existing nodes
+    pub fn sort(&mut self)
+    where
+        T: Ord,
+    {
+        self.sort_by(|a, b| a.cmp(b));
+    }
+
+    pub fn sort_by_key<K, F>(&mut self, mut f: F)
+    where
+        K: Ord,
+        F: FnMut(&T) -> K,
+    {
+        self.sort_by(|a, b| f(a).cmp(&f(b)));
+    }
 
-        let mut current = self
-            .head
-            .as_mut()
-            .expect("None case already handled, this cannot be None");
+    /// Bottom-up merge sort: in every pass we split the list into runs of `width` nodes,
+    /// merge neighbouring pairs of runs, and double the width, until a single run is left
+    pub fn sort_by<F>(&mut self, mut compare: F)
+    where
+        F: FnMut(&T, &T) -> Ordering,
+    {
+        let len = self.len;
+        let mut width = 1;
+        while width < len {
+            let rest = self.head.take();
+            self.tail = None;
+            let mut merge = MergeGuard {
+                list: self,
+                left: None,
+                right: None,
+                rest,
+            };
+            while let Some(left) = merge.rest {
+                merge.right = Self::split_after_n(left, width);
+                merge.rest = merge
+                    .right
+                    .and_then(|right| Self::split_after_n(right, width));
+                merge.left = Some(left);
+                merge.merge(&mut compare);
+            }
 
-        while let Some(ref mut node_next) = current.next {
-            current = node_next;
+            drop(merge);
+            width *= 2;
         }
+    }
 
-        current.next = other.head;
+    // Cuts the chain starting at head after n nodes and returns the part after the cut
+    fn split_after_n(head: NonNull<Node<T>>, n: usize) -> Link<T> {
+        let mut current = head;
+        // SAFETY: we only follow links between live nodes owned by the list
+        unsafe {
+            for _ in 1..n {
+                match (*current.as_ptr()).next {
+                    Some(next) => current = next,
+                    None => return None,
+                }
+            }
+            (*current.as_ptr()).next.take()
+        }
     }
 
-    pub fn iter(&self) -> LinkedListIter<T> {
+    pub fn iter(&self) -> LinkedListIter<'_, T> {
         LinkedListIter {
-            current: self.head.as_deref(),
+            // SAFETY: nodes live as long as the list, which is borrowed by the iterator
+            current: unsafe { Node::as_ref(self.head) },
+            last: unsafe { Node::as_ref(self.tail) },
+            len: self.len,
         }
     }
 
-    pub fn iter_mut(&mut self) -> LinkedListIterMut<T> {
+    pub fn iter_mut(&mut self) -> LinkedListIterMut<'_, T> {
         LinkedListIterMut {
-            current: self.head.as_mut(),
+            current: self.head,
+            last: self.tail,
+            len: self.len,
+            _marker: PhantomData,
+        }
+    }
+
+    /// Returns a read-only cursor pointing to the first element
+    pub fn cursor_front(&self) -> Cursor<'_, T> {
+        Cursor {
+            // SAFETY: nodes live as long as the list, which is borrowed by the cursor
+            current: unsafe { Node::as_ref(self.head) },
+            index: 0,
+        }
+    }
+
+    /// Returns a cursor pointing to the first element, that can edit the list in place.
+    /// Editing through the cursor is O(1) at cursor position, instead of walking from head
+    /// on every call like `insert_at` does
+    pub fn cursor_front_mut(&mut self) -> CursorMut<'_, T> {
+        let current = self.head;
+        CursorMut {
+            list: self,
+            prev: None,
+            current,
+            index: 0,
+        }
+    }
+
+    fn index_out_of_bounds(&self, index: usize) -> ! {
+        panic!(
+            "{}",
+            ListError::IndexOutOfBounds {
+                index,
+                len: self.len
+            }
+        )
+    }
+
+    // Walks from head to the node at index. Last node is reached directly through tail
+    fn node_at(&self, index: usize) -> Link<T> {
+        if index >= self.len {
+            return None;
+        }
+
+        if index == self.len - 1 {
+            return self.tail;
         }
+
+        let mut current = self.head;
+        for _ in 0..index {
+            // SAFETY: we only follow links between live nodes owned by this list
+            current = unsafe { (*current?.as_ptr()).next };
+        }
+        current
+    }
+}
+
+// Nodes are only linked with raw pointers, so we have to free them ourselves.
+// Popping them one by one also keeps drop from recursing on long lists
+impl<T> Drop for LinkedList<T> {
+    fn drop(&mut self) {
+        while self.pop_front().is_some() {}
     }
 }
+
 impl<T> IntoIterator for LinkedList<T> {
     type Item = T;
     type IntoIter = LinkedListIntoIter<T>;
@@ -204,62 +783,596 @@ impl<T> IntoIterator for LinkedList<T> {
     }
 }
 
-pub struct LinkedListIntoIter<T>(LinkedList<T>);
-
-impl<T> Iterator for LinkedListIntoIter<T> {
-    type Item = T;
-    fn next(&mut self) -> Option<Self::Item> {
-        self.0.pop_front()
+impl<'a, T> IntoIterator for &'a LinkedList<T> {
+    type Item = &'a T;
+    type IntoIter = LinkedListIter<'a, T>;
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
     }
 }
 
-pub struct LinkedListIter<'a, T> {
-    current: Option<&'a Node<T>>,
+impl<'a, T> IntoIterator for &'a mut LinkedList<T> {
+    type Item = &'a mut T;
+    type IntoIter = LinkedListIterMut<'a, T>;
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter_mut()
+    }
 }
 
-impl<'a, T> Iterator for LinkedListIter<'a, T> {
-    type Item = &'a T;
-    fn next(&mut self) -> Option<Self::Item> {
-        self.current.map(|n| {
-            self.current = n.next.as_deref();
-            &n.data
-        })
+impl<T> FromIterator<T> for LinkedList<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut list = LinkedList::new();
+        list.extend(iter);
+        list
     }
 }
 
-pub struct LinkedListIterMut<'a, T> {
-    current: Option<&'a mut Box<Node<T>>>,
+impl<T> Extend<T> for LinkedList<T> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        for data in iter {
+            self.push_back(data);
+        }
+    }
+}
+impl<'a, T: Copy + 'a> Extend<&'a T> for LinkedList<T> {
+    fn extend<I: IntoIterator<Item = &'a T>>(&mut self, iter: I) {
+        self.extend(iter.into_iter().copied());
+    }
 }
 
-impl<'a, T> Iterator for LinkedListIterMut<'a, T> {
-    type Item = &'a mut T;
-    fn next(&mut self) -> Option<Self::Item> {
-        self.current.take().map(|n| {
-            self.current = n.next.as_mut();
-            &mut n.data
-        })
+impl<T> From<Vec<T>> for LinkedList<T> {
+    fn from(vec: Vec<T>) -> Self {
+        vec.into_iter().collect()
     }
 }
 
-impl<T> Node<T> {
-    fn new(data: T) -> Self {
-        Node { data, next: None }
+impl<T, const N: usize> From<[T; N]> for LinkedList<T> {
+    fn from(array: [T; N]) -> Self {
+        array.into_iter().collect()
     }
+}
 
-    fn new_boxed(data: T, next: Option<Box<Node<T>>>) -> Option<Box<Node<T>>> {
-        match &next {
-            Some(_) => Some(Box::new(Node { data, next })),
-            None => Some(Box::new(Node::new(data))),
+/// Panics if index >= list.len()
+impl<T> Index<usize> for LinkedList<T> {
+    type Output = T;
+    fn index(&self, index: usize) -> &T {
+        match self.get(index) {
+            Some(data) => data,
+            None => self.index_out_of_bounds(index),
         }
     }
 }
 
-#[cfg(test)]
-mod test {
-    use super::*;
+/// Panics if index >= list.len()
+impl<T> IndexMut<usize> for LinkedList<T> {
+    fn index_mut(&mut self, index: usize) -> &mut T {
+        if index >= self.len {
+            self.index_out_of_bounds(index);
+        }
 
-    #[test]
-    fn push_back() {
+        self.get_mut(index).expect("index < len, so node exists")
+    }
+}
+
+impl<T: Clone> Clone for LinkedList<T> {
+    fn clone(&self) -> Self {
+        self.iter().cloned().collect()
+    }
+}
+
+impl<T: fmt::Debug> LinkedList<T> {
+    pub fn to_dot(&self) -> String {
+        let mut dot = Dot::new();
+        dot.chain(self.iter(), true);
+        dot.finish()
+    }
+}
+
+impl<T: fmt::Debug> fmt::Debug for LinkedList<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
+
+impl<T: fmt::Display> fmt::Display for LinkedList<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        display::arrows(self.iter(), f)
+    }
+}
+
+impl<T: PartialEq> PartialEq for LinkedList<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.iter().eq(other.iter())
+    }
+}
+
+impl<T: Eq> Eq for LinkedList<T> {}
+
+impl<T: PartialOrd> PartialOrd for LinkedList<T> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        self.iter().partial_cmp(other.iter())
+    }
+}
+
+impl<T: Ord> Ord for LinkedList<T> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.iter().cmp(other.iter())
+    }
+}
+
+// same as std::collections::LinkedList: length first, so that e.g. nested lists
+// [[1], [2]] and [[1, 2]] hash differently
+impl<T: Hash> Hash for LinkedList<T> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        state.write_usize(self.len);
+        for data in self.iter() {
+            data.hash(state);
+        }
+    }
+}
+
+impl<T> Sequence<T> for LinkedList<T> {
+    type Iter<'a>
+        = LinkedListIter<'a, T>
+    where
+        T: 'a;
+
+    fn len(&self) -> usize {
+        LinkedList::len(self)
+    }
+
+    fn push_front(&mut self, data: T) {
+        LinkedList::push_front(self, data)
+    }
+
+    fn push_back(&mut self, data: T) {
+        LinkedList::push_back(self, data)
+    }
+
+    fn pop_front(&mut self) -> Option<T> {
+        LinkedList::pop_front(self)
+    }
+
+    fn pop_back(&mut self) -> Option<T> {
+        LinkedList::pop_back(self)
+    }
+
+    fn iter(&self) -> LinkedListIter<'_, T> {
+        LinkedList::iter(self)
+    }
+}
+
+pub struct LinkedListIntoIter<T>(LinkedList<T>);
+
+impl<T> Iterator for LinkedListIntoIter<T> {
+    type Item = T;
+    fn next(&mut self) -> Option<Self::Item> {
+        self.0.pop_front()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.0.len, Some(self.0.len))
+    }
+
+    fn nth(&mut self, n: usize) -> Option<Self::Item> {
+        if n >= self.0.len {
+            // nothing left to yield, drop the rest in one go
+            self.0 = LinkedList::new();
+            return None;
+        }
+        for _ in 0..n {
+            self.0.pop_front();
+        }
+        self.0.pop_front()
+    }
+
+    fn last(mut self) -> Option<Self::Item> {
+        // no need to move every element out just to throw it away
+        self.0.pop_back()
+    }
+}
+
+impl<T> ExactSizeIterator for LinkedListIntoIter<T> {}
+
+impl<T> FusedIterator for LinkedListIntoIter<T> {}
+
+// The borrowing iterators always run up to the end of the list, so they keep the tail
+// around for `last`. There's no `DoubleEndedIterator` since nodes don't know their
+// predecessor, and walking back would make `.rev()` quadratic.
+pub struct LinkedListIter<'a, T> {
+    current: Option<&'a Node<T>>,
+    last: Option<&'a Node<T>>,
+    len: usize,
+}
+
+impl<'a, T> Iterator for LinkedListIter<'a, T> {
+    type Item = &'a T;
+    fn next(&mut self) -> Option<Self::Item> {
+        self.current.map(|n| {
+            // SAFETY: the list is borrowed for 'a, so its nodes stay alive and unchanged
+            self.current = unsafe { Node::as_ref(n.next) };
+            self.len -= 1;
+            &n.data
+        })
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.len, Some(self.len))
+    }
+
+    fn nth(&mut self, n: usize) -> Option<Self::Item> {
+        if n >= self.len {
+            self.current = None;
+            self.len = 0;
+            return None;
+        }
+        for _ in 0..n {
+            // SAFETY: same as in `next`, and there are more than n nodes left
+            self.current = self.current.and_then(|n| unsafe { Node::as_ref(n.next) });
+        }
+        self.len -= n;
+        self.next()
+    }
+
+    fn last(self) -> Option<Self::Item> {
+        if self.len == 0 {
+            return None;
+        }
+        self.last.map(|n| &n.data)
+    }
+}
+
+// SAFETY: the iterator only hands out `&T`, same as `&LinkedList<T>` would
+unsafe impl<T: Sync> Send for LinkedListIter<'_, T> {}
+unsafe impl<T: Sync> Sync for LinkedListIter<'_, T> {}
+
+impl<T> ExactSizeIterator for LinkedListIter<'_, T> {}
+
+impl<T> FusedIterator for LinkedListIter<'_, T> {}
+
+pub struct LinkedListIterMut<'a, T> {
+    current: Link<T>,
+    last: Link<T>,
+    len: usize,
+    _marker: PhantomData<&'a mut T>,
+}
+
+// SAFETY: the iterator hands out `&mut T`, same as `&mut LinkedList<T>` would
+unsafe impl<T: Send> Send for LinkedListIterMut<'_, T> {}
+unsafe impl<T: Sync> Sync for LinkedListIterMut<'_, T> {}
+
+impl<'a, T> Iterator for LinkedListIterMut<'a, T> {
+    type Item = &'a mut T;
+    fn next(&mut self) -> Option<Self::Item> {
+        self.current.map(|n| {
+            self.len -= 1;
+            // SAFETY: the list is mutably borrowed for 'a, and every node is yielded
+            // at most once, so the returned references never alias
+            unsafe {
+                self.current = (*n.as_ptr()).next;
+                &mut (*n.as_ptr()).data
+            }
+        })
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.len, Some(self.len))
+    }
+
+    fn nth(&mut self, n: usize) -> Option<Self::Item> {
+        if n >= self.len {
+            self.current = None;
+            self.len = 0;
+            return None;
+        }
+        for _ in 0..n {
+            // SAFETY: only reads the links of nodes that haven't been yielded yet
+            self.current = self.current.and_then(|n| unsafe { (*n.as_ptr()).next });
+        }
+        self.len -= n;
+        self.next()
+    }
+
+    fn last(self) -> Option<Self::Item> {
+        if self.len == 0 {
+            return None;
+        }
+        // SAFETY: with elements left, the tail hasn't been yielded yet, so this is
+        // the only reference to it
+        self.last.map(|n| unsafe { &mut (*n.as_ptr()).data })
+    }
+}
+
+impl<T> ExactSizeIterator for LinkedListIterMut<'_, T> {}
+
+impl<T> FusedIterator for LinkedListIterMut<'_, T> {}
+
+/// Iterator returned by `LinkedList::drain`. Drained elements are already unlinked
+/// from the list, so the ones that aren't yielded are dropped together with the iterator
+pub struct Drain<'a, T> {
+    removed: LinkedList<T>,
+    // keeps the list borrowed while draining, same as std drain iterators
+    _marker: PhantomData<&'a mut LinkedList<T>>,
+}
+
+impl<T> Iterator for Drain<'_, T> {
+    type Item = T;
+    fn next(&mut self) -> Option<Self::Item> {
+        self.removed.pop_front()
+    }
+}
+
+/// Iterator returned by `LinkedList::extract_if`
+pub struct ExtractIf<'a, T, F>
+where
+    F: FnMut(&mut T) -> bool,
+{
+    cursor: CursorMut<'a, T>,
+    predicate: F,
+}
+
+impl<T, F> Iterator for ExtractIf<'_, T, F>
+where
+    F: FnMut(&mut T) -> bool,
+{
+    type Item = T;
+    fn next(&mut self) -> Option<Self::Item> {
+        while let Some(data) = self.cursor.current() {
+            if (self.predicate)(data) {
+                return self.cursor.remove_current();
+            }
+            self.cursor.move_next();
+        }
+        None
+    }
+}
+
+/// Read-only cursor. It can only move forward, and after moving past the last element
+/// it points to nothing
+pub struct Cursor<'a, T> {
+    current: Option<&'a Node<T>>,
+    index: usize,
+}
+
+// SAFETY: the cursor only reads the list, same as `&LinkedList<T>` would
+unsafe impl<T: Sync> Send for Cursor<'_, T> {}
+unsafe impl<T: Sync> Sync for Cursor<'_, T> {}
+
+impl<'a, T> Cursor<'a, T> {
+    /// Index of the element cursor points to, or None if cursor is past the end
+    pub fn index(&self) -> Option<usize> {
+        self.current.map(|_| self.index)
+    }
+
+    pub fn current(&self) -> Option<&'a T> {
+        self.current.map(|n| &n.data)
+    }
+
+    pub fn peek_next(&self) -> Option<&'a T> {
+        // SAFETY: the list is borrowed for 'a, so its nodes stay alive and unchanged
+        self.current
+            .and_then(|n| unsafe { Node::as_ref(n.next) })
+            .map(|n| &n.data)
+    }
+
+    pub fn move_next(&mut self) {
+        if let Some(node) = self.current {
+            // SAFETY: the list is borrowed for 'a, so its nodes stay alive and unchanged
+            self.current = unsafe { Node::as_ref(node.next) };
+            self.index += 1;
+        }
+    }
+}
+
+/// Cursor that can edit the list in place. Same as `Cursor`, it can only move forward
+pub struct CursorMut<'a, T> {
+    list: &'a mut LinkedList<T>,
+    // Node before the current one, so that we're able to unlink current node.
+    // None when cursor is at head. When cursor is past the end, this is the last node
+    prev: Link<T>,
+    // None when cursor is past the end
+    current: Link<T>,
+    index: usize,
+}
+
+// SAFETY: the cursor edits the list it borrows, same as `&mut LinkedList<T>` would
+unsafe impl<T: Send> Send for CursorMut<'_, T> {}
+unsafe impl<T: Sync> Sync for CursorMut<'_, T> {}
+
+// SAFETY for all unsafe blocks below: `prev` and `current` always point to live nodes
+// of `list`, which the cursor borrows mutably for 'a
+impl<'a, T> CursorMut<'a, T> {
+    /// Index of the element cursor points to, or None if cursor is past the end
+    pub fn index(&self) -> Option<usize> {
+        self.current.map(|_| self.index)
+    }
+
+    pub fn current(&mut self) -> Option<&mut T> {
+        self.current.map(|n| unsafe { &mut (*n.as_ptr()).data })
+    }
+
+    pub fn peek_next(&mut self) -> Option<&mut T> {
+        let next = unsafe { (*self.current?.as_ptr()).next };
+        next.map(|n| unsafe { &mut (*n.as_ptr()).data })
+    }
+
+    /// Moves to the next element. If cursor is past the end, it stays there
+    pub fn move_next(&mut self) {
+        if let Some(current) = self.current {
+            self.prev = Some(current);
+            self.current = unsafe { (*current.as_ptr()).next };
+            self.index += 1;
+        }
+    }
+
+    /// Inserts data after the current element, cursor stays where it is.
+    /// If cursor is past the end, data is appended to the list and cursor stays past the end
+    pub fn insert_after(&mut self, data: T) {
+        match self.current {
+            Some(current) => unsafe {
+                let node = Node::new_raw(data, (*current.as_ptr()).next);
+                (*current.as_ptr()).next = Some(node);
+                if self.list.tail == Some(current) {
+                    self.list.tail = Some(node);
+                }
+                self.list.len += 1;
+            },
+            None => {
+                self.list.push_back(data);
+                self.prev = self.list.tail;
+                self.index += 1;
+            }
+        }
+    }
+
+    /// Removes the current element and returns it. Cursor moves to the next element
+    pub fn remove_current(&mut self) -> Option<T> {
+        let current = self.current?;
+        // we unlink current right now, so nothing else will reach it afterwards
+        let node = unsafe { Box::from_raw(current.as_ptr()) };
+        match self.prev {
+            Some(prev) => unsafe { (*prev.as_ptr()).next = node.next },
+            None => self.list.head = node.next,
+        }
+        if self.list.tail == Some(current) {
+            self.list.tail = self.prev;
+        }
+        self.list.len -= 1;
+        self.current = node.next;
+        Some(node.data)
+    }
+
+    /// Splits the list after the current element and returns everything after it.
+    /// If cursor is past the end, returns an empty list
+    pub fn split_after(&mut self) -> LinkedList<T> {
+        let mut second = LinkedList::new();
+        let Some(current) = self.current else {
+            return second;
+        };
+
+        second.head = unsafe { (*current.as_ptr()).next.take() };
+        if second.head.is_some() {
+            second.tail = self.list.tail;
+            second.len = self.list.len - self.index - 1;
+            self.list.tail = Some(current);
+            self.list.len = self.index + 1;
+        }
+        second
+    }
+
+    /// Inserts all elements of other after the current element, cursor stays where it is.
+    /// If cursor is past the end, other is appended to the list and cursor stays past the end
+    pub fn splice_after(&mut self, mut other: LinkedList<T>) {
+        let Some(current) = self.current else {
+            self.index += other.len;
+            self.list.merge(other);
+            self.prev = self.list.tail;
+            return;
+        };
+
+        let (Some(other_head), Some(other_tail)) = (other.head.take(), other.tail.take()) else {
+            return;
+        };
+
+        unsafe {
+            (*other_tail.as_ptr()).next = (*current.as_ptr()).next;
+            (*current.as_ptr()).next = Some(other_head);
+        }
+        if self.list.tail == Some(current) {
+            self.list.tail = Some(other_tail);
+        }
+        self.list.len += mem::take(&mut other.len);
+    }
+}
+
+// Merge in progress. Merged nodes are already linked onto the end of `list`, the others
+// wait in `left`, `right` and `rest`. If `compare` panics, dropping the guard links the
+// waiting chains onto the list as well, so the list keeps all its nodes and stays valid,
+// just not sorted. `len` doesn't change while merging
+struct MergeGuard<'a, T> {
+    list: &'a mut LinkedList<T>,
+    left: Link<T>,
+    right: Link<T>,
+    rest: Link<T>,
+}
+
+impl<T> MergeGuard<'_, T> {
+    // Moves all of `left` and `right` to the list. On equal elements left goes first,
+    // which keeps merging stable
+    fn merge<F>(&mut self, compare: &mut F)
+    where
+        F: FnMut(&T, &T) -> Ordering,
+    {
+        while let (Some(l), Some(r)) = (self.left, self.right) {
+            // SAFETY: both are live nodes waiting to be merged
+            unsafe {
+                if compare(&(*l.as_ptr()).data, &(*r.as_ptr()).data) == Ordering::Greater {
+                    self.right = (*r.as_ptr()).next;
+                    self.push(r);
+                } else {
+                    self.left = (*l.as_ptr()).next;
+                    self.push(l);
+                }
+            }
+        }
+
+        let left = self.left.take();
+        self.push_chain(left);
+        let right = self.right.take();
+        self.push_chain(right);
+    }
+
+    // Links node after the last merged node. Its own `next` is fixed by the next push,
+    // or when the guard is dropped
+    fn push(&mut self, node: NonNull<Node<T>>) {
+        match self.list.tail {
+            // SAFETY: tail is the last merged node, owned by the list
+            Some(tail) => unsafe { (*tail.as_ptr()).next = Some(node) },
+            None => self.list.head = Some(node),
+        }
+        self.list.tail = Some(node);
+    }
+
+    fn push_chain(&mut self, mut chain: Link<T>) {
+        while let Some(node) = chain {
+            // SAFETY: node is a live node waiting to be merged
+            chain = unsafe { (*node.as_ptr()).next };
+            self.push(node);
+        }
+    }
+}
+
+impl<T> Drop for MergeGuard<'_, T> {
+    fn drop(&mut self) {
+        for chain in [self.left.take(), self.right.take(), self.rest.take()] {
+            self.push_chain(chain);
+        }
+        if let Some(tail) = self.list.tail {
+            // SAFETY: tail is the last node of the list
+            unsafe { (*tail.as_ptr()).next = None };
+        }
+    }
+}
+
+impl<T> Node<T> {
+    fn new_raw(data: T, next: Link<T>) -> NonNull<Node<T>> {
+        let node = Box::new(Node { data, next });
+        // SAFETY: pointer returned by `Box::into_raw` is never null
+        unsafe { NonNull::new_unchecked(Box::into_raw(node)) }
+    }
+
+    // SAFETY: caller must make sure the node stays alive and isn't mutated for 'a
+    unsafe fn as_ref<'a>(link: Link<T>) -> Option<&'a Node<T>> {
+        link.map(|n| &*n.as_ptr())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn push_back() {
         let mut list = LinkedList::new();
         assert_eq!(list.len(), 0);
         list.push_back(1);
@@ -459,7 +1572,14 @@ mod test {
         list.push_back(3);
         list.push_back(4);
         let r = list.split_at(4);
-        assert!(r.is_err())
+        assert!(r.is_err());
+        assert_eq!(
+            r.unwrap_err(),
+            ListError::IndexOutOfBounds { index: 4, len: 4 }
+        );
+
+        let list: LinkedList<i32> = LinkedList::new();
+        assert_eq!(list.split_at(0).unwrap_err(), ListError::Empty);
     }
 
     #[test]
@@ -553,4 +1673,911 @@ mod test {
         assert_eq!(iter.next(), Some(&1));
         assert_eq!(iter.next(), Some(&4));
     }
+
+    #[test]
+    fn drop_long_list() {
+        let mut list = LinkedList::new();
+        for i in 0..2_000_000 {
+            list.push_front(i);
+        }
+        drop(list);
+    }
+
+    #[test]
+    fn cursor() {
+        let mut list = LinkedList::new();
+        let cursor = list.cursor_front();
+        assert_eq!(cursor.current(), None);
+        assert_eq!(cursor.index(), None);
+
+        list.push_back(1);
+        list.push_back(2);
+        let mut cursor = list.cursor_front();
+        assert_eq!(cursor.current(), Some(&1));
+        assert_eq!(cursor.peek_next(), Some(&2));
+        assert_eq!(cursor.index(), Some(0));
+        cursor.move_next();
+        assert_eq!(cursor.current(), Some(&2));
+        assert_eq!(cursor.peek_next(), None);
+        assert_eq!(cursor.index(), Some(1));
+        cursor.move_next();
+        assert_eq!(cursor.current(), None);
+        assert_eq!(cursor.index(), None);
+        cursor.move_next();
+        assert_eq!(cursor.current(), None);
+    }
+
+    #[test]
+    fn cursor_mut_move_and_edit() {
+        let mut list = LinkedList::new();
+        list.push_back(1);
+        list.push_back(2);
+        list.push_back(3);
+
+        let mut cursor = list.cursor_front_mut();
+        assert_eq!(cursor.current(), Some(&mut 1));
+        *cursor.peek_next().unwrap() = 20;
+        cursor.move_next();
+        assert_eq!(cursor.index(), Some(1));
+        *cursor.current().unwrap() += 1;
+        cursor.move_next();
+        cursor.move_next();
+        assert_eq!(cursor.current(), None);
+        assert_eq!(cursor.index(), None);
+        cursor.move_next();
+        assert_eq!(cursor.current(), None);
+
+        assert!(list.iter().eq([1, 21, 3].iter()));
+    }
+
+    #[test]
+    fn cursor_mut_insert_after() {
+        let mut list = LinkedList::new();
+        let mut cursor = list.cursor_front_mut();
+        cursor.insert_after(1);
+        assert_eq!(cursor.current(), None);
+        cursor.insert_after(3);
+
+        let mut cursor = list.cursor_front_mut();
+        cursor.insert_after(2);
+        assert_eq!(cursor.current(), Some(&mut 1));
+        cursor.move_next();
+        cursor.move_next();
+        cursor.insert_after(4);
+
+        assert!(list.iter().eq([1, 2, 3, 4].iter()));
+    }
+
+    #[test]
+    fn cursor_mut_remove_current() {
+        let mut list = LinkedList::new();
+        for i in 1..=6 {
+            list.push_back(i);
+        }
+
+        // remove all even numbers in a single pass
+        let mut cursor = list.cursor_front_mut();
+        while let Some(data) = cursor.current() {
+            if *data % 2 == 0 {
+                cursor.remove_current();
+            } else {
+                cursor.move_next();
+            }
+        }
+        assert_eq!(cursor.remove_current(), None);
+        assert!(list.iter().eq([1, 3, 5].iter()));
+
+        let mut cursor = list.cursor_front_mut();
+        assert_eq!(cursor.remove_current(), Some(1));
+        assert_eq!(cursor.remove_current(), Some(3));
+        assert_eq!(cursor.remove_current(), Some(5));
+        assert_eq!(cursor.remove_current(), None);
+        assert!(list.is_empty());
+    }
+
+    #[test]
+    fn cursor_mut_split_after() {
+        let mut list = LinkedList::new();
+        list.push_back(1);
+        list.push_back(2);
+        list.push_back(3);
+
+        let mut cursor = list.cursor_front_mut();
+        cursor.move_next();
+        let second = cursor.split_after();
+        assert_eq!(cursor.current(), Some(&mut 2));
+        assert!(second.iter().eq([3].iter()));
+        assert!(list.iter().eq([1, 2].iter()));
+
+        let mut cursor = list.cursor_front_mut();
+        cursor.move_next();
+        cursor.move_next();
+        assert!(cursor.split_after().is_empty());
+        assert_eq!(list.len(), 2);
+    }
+
+    #[test]
+    fn cursor_mut_splice_after() {
+        let mut list = LinkedList::new();
+        list.push_back(1);
+        list.push_back(4);
+        let mut other = LinkedList::new();
+        other.push_back(2);
+        other.push_back(3);
+
+        let mut cursor = list.cursor_front_mut();
+        cursor.splice_after(other);
+        assert_eq!(cursor.current(), Some(&mut 1));
+        cursor.splice_after(LinkedList::new());
+        cursor.move_next();
+        assert_eq!(cursor.current(), Some(&mut 2));
+        assert!(list.iter().eq([1, 2, 3, 4].iter()));
+
+        let mut other = LinkedList::new();
+        other.push_back(5);
+        other.push_back(6);
+        let mut cursor = list.cursor_front_mut();
+        for _ in 0..4 {
+            cursor.move_next();
+        }
+        cursor.splice_after(other);
+        assert_eq!(cursor.current(), None);
+        cursor.insert_after(7);
+        assert!(list.iter().eq([1, 2, 3, 4, 5, 6, 7].iter()));
+    }
+
+    #[test]
+    fn sort() {
+        let mut list: LinkedList<i32> = LinkedList::new();
+        list.sort();
+        assert!(list.is_empty());
+
+        list.push_back(1);
+        list.sort();
+        assert!(list.iter().eq([1].iter()));
+
+        for i in [5, 3, 8, -1, 3, 0, 7] {
+            list.push_back(i);
+        }
+        list.sort();
+        assert!(list.iter().eq([-1, 0, 1, 3, 3, 5, 7, 8].iter()));
+
+        list.sort_by(|a, b| b.cmp(a));
+        assert!(list.iter().eq([8, 7, 5, 3, 3, 1, 0, -1].iter()));
+    }
+
+    #[test]
+    fn sort_is_stable() {
+        let mut list = LinkedList::new();
+        list.push_back((2, 'a'));
+        list.push_back((1, 'b'));
+        list.push_back((2, 'c'));
+        list.push_back((1, 'd'));
+        list.push_back((0, 'e'));
+        list.sort_by_key(|&(key, _)| key);
+        assert!(list
+            .iter()
+            .eq([(0, 'e'), (1, 'b'), (1, 'd'), (2, 'a'), (2, 'c')].iter()));
+    }
+
+    #[test]
+    fn sort_long_list() {
+        let mut list = LinkedList::new();
+        for i in 0..100_000 {
+            list.push_front((i * 7919) % 100_003);
+        }
+        list.sort();
+        assert_eq!(list.len(), 100_000);
+        let mut iter = list.iter();
+        let mut previous = iter.next().unwrap();
+        for current in iter {
+            assert!(previous <= current);
+            previous = current;
+        }
+    }
+
+    #[test]
+    fn sort_with_panicking_compare() {
+        let mut list: LinkedList<i32> = (0..20).rev().collect();
+        let mut calls = 0;
+        let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+            list.sort_by(|a, b| {
+                calls += 1;
+                assert!(calls < 30, "compare panicked");
+                a.cmp(b)
+            })
+        }));
+        assert!(result.is_err());
+
+        // every element is still there, in some order, and the list is usable
+        assert_eq!(list.len(), 20);
+        let mut items: Vec<_> = list.iter().copied().collect();
+        items.sort();
+        assert!(items.into_iter().eq(0..20));
+        list.push_back(20);
+        assert_eq!(list.iter().count(), 21);
+        assert_eq!(list.iter().last(), Some(&20));
+        list.sort();
+        assert!(list.iter().copied().eq(0..21));
+    }
+
+    #[test]
+    fn merge_sorted() {
+        let mut list = LinkedList::new();
+        list.push_back(1);
+        list.push_back(4);
+        list.push_back(6);
+        let mut other = LinkedList::new();
+        other.push_back(2);
+        other.push_back(3);
+        other.push_back(7);
+        other.push_back(8);
+        list.merge_sorted(other, |a, b| a.cmp(b));
+        assert!(list.iter().eq([1, 2, 3, 4, 6, 7, 8].iter()));
+
+        list.merge_sorted(LinkedList::new(), |a, b| a.cmp(b));
+        assert_eq!(list.len(), 7);
+
+        let mut empty = LinkedList::new();
+        empty.merge_sorted(list, |a, b| a.cmp(b));
+        assert!(empty.iter().eq([1, 2, 3, 4, 6, 7, 8].iter()));
+    }
+
+    #[test]
+    fn merge_sorted_with_panicking_compare() {
+        let mut list = list![1, 3, 5];
+        let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+            list.merge_sorted(list![2, 4], |a, b| {
+                assert!(*a < 3, "compare panicked");
+                a.cmp(b)
+            })
+        }));
+        assert!(result.is_err());
+
+        assert_eq!(list.len(), 5);
+        let mut items: Vec<_> = list.iter().copied().collect();
+        items.sort();
+        assert_eq!(items, [1, 2, 3, 4, 5]);
+        list.push_back(6);
+        assert_eq!(list.iter().count(), 6);
+        assert_eq!(list.iter().last(), Some(&6));
+    }
+
+    #[test]
+    fn merge_sorted_keeps_self_first_on_ties() {
+        let mut list = LinkedList::new();
+        list.push_back((1, "self"));
+        list.push_back((3, "self"));
+        let mut other = LinkedList::new();
+        other.push_back((1, "other"));
+        other.push_back((2, "other"));
+        other.push_back((3, "other"));
+        list.merge_sorted(other, |a, b| a.0.cmp(&b.0));
+        assert!(list.iter().eq([
+            (1, "self"),
+            (1, "other"),
+            (2, "other"),
+            (3, "self"),
+            (3, "other")
+        ]
+        .iter()));
+    }
+
+    #[test]
+    fn from_iterator_and_extend() {
+        let mut list: LinkedList<_> = (1..=3).collect();
+        assert!(list.iter().eq([1, 2, 3].iter()));
+
+        list.extend(4..=5);
+        list.extend(&[6, 7]);
+        assert!(list.iter().eq([1, 2, 3, 4, 5, 6, 7].iter()));
+
+        let mut empty = LinkedList::new();
+        empty.extend(Vec::<i32>::new());
+        assert!(empty.is_empty());
+        empty.extend(vec![1]);
+        assert_eq!(empty.len(), 1);
+    }
+
+    #[test]
+    fn from_vec_array_and_macro() {
+        let from_vec = LinkedList::from(vec![1, 2, 3]);
+        let from_array = LinkedList::from([1, 2, 3]);
+        assert_eq!(from_vec, from_array);
+        assert_eq!(list![1, 2, 3], from_array);
+        assert_eq!(list![1, 2, 3,], from_array);
+
+        let empty: LinkedList<i32> = list![];
+        assert!(empty.is_empty());
+    }
+
+    #[test]
+    fn clone() {
+        let list = list![String::from("a"), String::from("b")];
+        let mut cloned = list.clone();
+        cloned.push_back(String::from("c"));
+        assert_eq!(list.len(), 2);
+        assert_eq!(cloned.len(), 3);
+    }
+
+    #[test]
+    fn debug() {
+        assert_eq!(format!("{:?}", list![1, 2, 3]), "[1, 2, 3]");
+        assert_eq!(format!("{:?}", LinkedList::<i32>::new()), "[]");
+        assert_eq!(format!("{:#?}", list![1]), "[\n    1,\n]");
+        assert_eq!(format!("{:>2?}", list![1, 2]), "[ 1,  2]");
+    }
+
+    #[test]
+    fn display() {
+        assert_eq!(list![1, 2, 3].to_string(), "1 -> 2 -> 3 -> ∅");
+        assert_eq!(LinkedList::<i32>::new().to_string(), "∅");
+        assert_eq!(format!("{:.1}", list![1.25, 2.0]), "1.2 -> 2.0 -> ∅");
+        assert_eq!(format!("{:<3}", list!["a", "b"]), "a   -> b   -> ∅");
+    }
+
+    #[test]
+    fn eq_and_ord() {
+        assert_eq!(list![1, 2], list![1, 2]);
+        assert_ne!(list![1, 2], list![1, 2, 3]);
+        assert_ne!(list![1, 2], list![2, 1]);
+
+        assert!(list![1, 2] < list![1, 3]);
+        assert!(list![1, 2] < list![1, 2, 0]);
+        assert!(LinkedList::new() < list![0]);
+        assert_eq!(list![3].cmp(&list![1, 2]), Ordering::Greater);
+        assert_eq!(
+            list![1.0, f64::NAN].partial_cmp(&list![1.0, f64::NAN]),
+            None
+        );
+    }
+
+    #[test]
+    fn hash() {
+        use std::collections::HashSet;
+
+        let mut set = HashSet::new();
+        assert!(set.insert(list![1, 2, 3]));
+        assert!(set.insert(list![3, 2, 1]));
+        assert!(!set.insert(list![1, 2, 3]));
+        assert!(set.contains(&list![3, 2, 1]));
+        assert_eq!(set.len(), 2);
+    }
+
+    #[test]
+    fn into_iter_for_references() {
+        let mut list = list![1, 2, 3];
+        for data in &mut list {
+            *data *= 10;
+        }
+
+        let mut sum = 0;
+        for data in &list {
+            sum += data;
+        }
+        assert_eq!(sum, 60);
+    }
+
+    #[test]
+    fn try_insert_at() {
+        let mut list = LinkedList::new();
+        assert_eq!(
+            list.try_insert_at(1, 1),
+            Err(ListError::IndexOutOfBounds { index: 1, len: 0 })
+        );
+        assert_eq!(list.try_insert_at(0, 1), Ok(()));
+        assert_eq!(list.try_insert_at(1, 3), Ok(()));
+        assert_eq!(list.try_insert_at(1, 2), Ok(()));
+        assert_eq!(
+            list.try_insert_at(4, 4),
+            Err(ListError::IndexOutOfBounds { index: 4, len: 3 })
+        );
+        assert_eq!(list, list![1, 2, 3]);
+    }
+
+    #[test]
+    fn try_get() {
+        let list = list![1, 2, 3];
+        assert_eq!(list.try_get(0), Ok(&1));
+        assert_eq!(list.try_get(2), Ok(&3));
+        assert_eq!(
+            list.try_get(3),
+            Err(ListError::IndexOutOfBounds { index: 3, len: 3 })
+        );
+        assert_eq!(LinkedList::<i32>::new().try_get(0), Err(ListError::Empty));
+    }
+
+    #[test]
+    fn try_remove_at() {
+        let mut list = list![1, 2, 3, 4];
+        assert_eq!(list.try_remove_at(1), Ok(2));
+        assert_eq!(list.try_remove_at(2), Ok(4));
+        assert_eq!(
+            list.try_remove_at(2),
+            Err(ListError::IndexOutOfBounds { index: 2, len: 2 })
+        );
+        assert_eq!(list.try_remove_at(0), Ok(1));
+        assert_eq!(list.try_remove_at(0), Ok(3));
+        assert_eq!(list.try_remove_at(0), Err(ListError::Empty));
+    }
+
+    // every operation that can change the last node must keep tail and len in sync,
+    // otherwise push_back would link to a wrong node
+    #[test]
+    fn tail_and_len_stay_in_sync() {
+        fn check(list: &mut LinkedList<i32>, expected: &[i32]) {
+            assert_eq!(list.len(), expected.len());
+            assert!(list.iter().eq(expected.iter()));
+            list.push_back(100);
+            assert_eq!(list.pop_back(), Some(100));
+            assert!(list.iter().eq(expected.iter()));
+        }
+
+        let mut list = list![1, 2, 3];
+        list.pop_back();
+        check(&mut list, &[1, 2]);
+
+        list.insert_at(2, 3);
+        check(&mut list, &[1, 2, 3]);
+
+        list.try_remove_at(2).unwrap();
+        check(&mut list, &[1, 2]);
+
+        let (mut first, mut second) = list.split_at(0).unwrap();
+        check(&mut first, &[1]);
+        check(&mut second, &[2]);
+
+        first.merge(second);
+        check(&mut first, &[1, 2]);
+
+        let mut list = list![3, 1, 2];
+        list.sort();
+        check(&mut list, &[1, 2, 3]);
+
+        list.merge_sorted(list![0, 4], |a, b| a.cmp(b));
+        check(&mut list, &[0, 1, 2, 3, 4]);
+
+        let mut cursor = list.cursor_front_mut();
+        for _ in 0..4 {
+            cursor.move_next();
+        }
+        cursor.remove_current();
+        check(&mut list, &[0, 1, 2, 3]);
+
+        let mut cursor = list.cursor_front_mut();
+        cursor.move_next();
+        let mut second = cursor.split_after();
+        check(&mut list, &[0, 1]);
+        check(&mut second, &[2, 3]);
+
+        let mut cursor = list.cursor_front_mut();
+        cursor.move_next();
+        cursor.splice_after(list![5, 6]);
+        cursor.insert_after(4);
+        check(&mut list, &[0, 1, 4, 5, 6]);
+
+        while list.pop_front().is_some() {}
+        check(&mut list, &[]);
+    }
+
+    #[test]
+    fn get() {
+        let mut list = list![1, 2, 3];
+        assert_eq!(list.get(0), Some(&1));
+        assert_eq!(list.get(2), Some(&3));
+        assert_eq!(list.get(3), None);
+        assert_eq!(LinkedList::<i32>::new().get(0), None);
+
+        *list.get_mut(1).unwrap() = 20;
+        assert_eq!(list.get_mut(3), None);
+        assert_eq!(list, list![1, 20, 3]);
+    }
+
+    #[test]
+    fn remove_at() {
+        let mut list = list![1, 2, 3];
+        assert_eq!(list.remove_at(3), None);
+        assert_eq!(list.remove_at(2), Some(3));
+        assert_eq!(list.remove_at(0), Some(1));
+        assert_eq!(list.remove_at(0), Some(2));
+        assert_eq!(list.remove_at(0), None);
+        assert!(list.is_empty());
+    }
+
+    #[test]
+    fn replace_at() {
+        let mut list = list![1, 2, 3];
+        assert_eq!(list.replace_at(1, 20), Some(2));
+        assert_eq!(list.replace_at(3, 40), None);
+        assert_eq!(list, list![1, 20, 3]);
+    }
+
+    #[test]
+    fn swap() {
+        let mut list = list![1, 2, 3, 4];
+        list.swap(0, 3);
+        assert_eq!(list, list![4, 2, 3, 1]);
+        list.swap(2, 1);
+        assert_eq!(list, list![4, 3, 2, 1]);
+        list.swap(1, 1);
+        assert_eq!(list, list![4, 3, 2, 1]);
+    }
+
+    #[test]
+    #[should_panic(expected = "index 4 is out of bounds for list of length 4")]
+    fn swap_out_of_bounds() {
+        let mut list = list![1, 2, 3, 4];
+        list.swap(0, 4);
+    }
+
+    #[test]
+    fn index() {
+        let mut list = list![1, 2, 3];
+        assert_eq!(list[0], 1);
+        assert_eq!(list[2], 3);
+        list[1] += 10;
+        assert_eq!(list[1], 12);
+    }
+
+    #[test]
+    #[should_panic(expected = "index 3 is out of bounds for list of length 3")]
+    fn index_out_of_bounds() {
+        let list = list![1, 2, 3];
+        let _ = list[3];
+    }
+
+    #[test]
+    #[should_panic(expected = "index 0 is out of bounds for list of length 0")]
+    fn index_mut_out_of_bounds() {
+        let mut list: LinkedList<i32> = LinkedList::new();
+        list[0] = 1;
+    }
+
+    #[test]
+    fn search_by_value() {
+        let list = list![1, 2, 3, 2, 1];
+        assert!(list.contains(&3));
+        assert!(!list.contains(&4));
+        assert_eq!(list.last_index_of(&2), Some(3));
+        assert_eq!(list.last_index_of(&3), Some(2));
+        assert_eq!(list.last_index_of(&4), None);
+        assert_eq!(list.all_indices_of(&1), vec![0, 4]);
+        assert_eq!(list.all_indices_of(&4), Vec::<usize>::new());
+    }
+
+    #[test]
+    fn search_by_predicate() {
+        let list = list![1, 2, 3, 4, 5];
+        assert_eq!(list.position(|d| *d > 2), Some(2));
+        assert_eq!(list.position(|d| *d > 5), None);
+        assert_eq!(list.rposition(|d| d % 2 == 0), Some(3));
+        assert_eq!(list.rposition(|d| *d > 5), None);
+        assert_eq!(list.find(|d| d % 2 == 0), Some(&2));
+        assert_eq!(list.find(|d| *d > 5), None);
+    }
+
+    #[test]
+    fn search_point_by_field() {
+        #[derive(Debug, PartialEq)]
+        struct Point {
+            x: i32,
+            y: i32,
+        }
+
+        let mut list = list![
+            Point { x: 1, y: 2 },
+            Point { x: 2, y: 3 },
+            Point { x: 3, y: 4 }
+        ];
+        assert_eq!(list.position(|p| p.x == 2), Some(1));
+
+        list.find_mut(|p| p.y == 4).unwrap().x = 30;
+        assert_eq!(list.find(|p| p.y == 4), Some(&Point { x: 30, y: 4 }));
+        assert_eq!(list.find_mut(|p| p.y == 5), None);
+
+        assert_eq!(list.remove_first(|p| p.x > 1), Some(Point { x: 2, y: 3 }));
+        assert_eq!(list.remove_first(|p| p.x > 100), None);
+        assert_eq!(list.remove_first(|p| p.x > 1), Some(Point { x: 30, y: 4 }));
+        assert_eq!(list.len(), 1);
+        list.push_back(Point { x: 5, y: 6 });
+        assert_eq!(list.position(|p| p.x == 5), Some(1));
+    }
+
+    #[test]
+    fn retain() {
+        let mut list = list![1, 2, 3, 4, 5, 6];
+        list.retain(|d| d % 2 == 1);
+        assert_eq!(list, list![1, 3, 5]);
+        list.push_back(7);
+        assert_eq!(list.len(), 4);
+
+        list.retain(|_| false);
+        assert!(list.is_empty());
+        list.push_back(1);
+        assert_eq!(list, list![1]);
+    }
+
+    #[test]
+    fn retain_mut() {
+        let mut list = list![1, 2, 3, 4];
+        list.retain_mut(|d| {
+            *d *= 10;
+            *d != 20
+        });
+        assert_eq!(list, list![10, 30, 40]);
+    }
+
+    #[test]
+    fn extract_if() {
+        let mut list = list![1, 2, 3, 4, 5, 6];
+        let evens: Vec<_> = list.extract_if(|d| *d % 2 == 0).collect();
+        assert_eq!(evens, vec![2, 4, 6]);
+        assert_eq!(list, list![1, 3, 5]);
+        list.push_back(7);
+        assert_eq!(list.len(), 4);
+
+        // elements after the last yielded one stay in the list
+        let mut list = list![1, 2, 3, 4];
+        assert_eq!(list.extract_if(|d| *d > 1).next(), Some(2));
+        assert_eq!(list, list![1, 3, 4]);
+    }
+
+    #[test]
+    fn dedup() {
+        let mut list = list![1, 1, 2, 3, 3, 3, 1, 4, 4];
+        list.dedup();
+        assert_eq!(list, list![1, 2, 3, 1, 4]);
+        list.push_back(5);
+        assert_eq!(list.len(), 6);
+
+        let mut list: LinkedList<i32> = LinkedList::new();
+        list.dedup();
+        assert!(list.is_empty());
+
+        let mut list = list![7, 7, 7];
+        list.dedup();
+        assert_eq!(list, list![7]);
+        list.push_back(8);
+        assert_eq!(list, list![7, 8]);
+    }
+
+    #[test]
+    fn dedup_by_key() {
+        let mut list = list![10, 11, 20, 21, 22, 30, 12];
+        list.dedup_by_key(|d| *d / 10);
+        assert_eq!(list, list![10, 20, 30, 12]);
+
+        let mut list = list!["a", "A", "b", "B", "b"];
+        list.dedup_by(|a, b| a.eq_ignore_ascii_case(b));
+        assert_eq!(list, list!["a", "b"]);
+    }
+
+    #[test]
+    fn reverse() {
+        let mut list = list![1, 2, 3, 4];
+        list.reverse();
+        assert_eq!(list, list![4, 3, 2, 1]);
+        list.push_back(0);
+        assert_eq!(list, list![4, 3, 2, 1, 0]);
+
+        let mut list = list![1];
+        list.reverse();
+        assert_eq!(list, list![1]);
+
+        let mut list: LinkedList<i32> = LinkedList::new();
+        list.reverse();
+        assert!(list.is_empty());
+    }
+
+    #[test]
+    fn rotate() {
+        let mut list = list![1, 2, 3, 4, 5];
+        list.rotate_left(2);
+        assert_eq!(list, list![3, 4, 5, 1, 2]);
+        list.rotate_right(2);
+        assert_eq!(list, list![1, 2, 3, 4, 5]);
+        list.rotate_right(1);
+        assert_eq!(list, list![5, 1, 2, 3, 4]);
+        list.rotate_left(0);
+        list.rotate_left(5);
+        list.rotate_right(5);
+        assert_eq!(list, list![5, 1, 2, 3, 4]);
+        list.push_back(6);
+        assert_eq!(list, list![5, 1, 2, 3, 4, 6]);
+
+        let mut list: LinkedList<i32> = LinkedList::new();
+        list.rotate_left(0);
+        list.rotate_right(0);
+        assert!(list.is_empty());
+    }
+
+    #[test]
+    #[should_panic(expected = "cannot rotate list of length 2 by 3")]
+    fn rotate_out_of_bounds() {
+        let mut list = list![1, 2];
+        list.rotate_left(3);
+    }
+
+    #[test]
+    fn splice_at() {
+        let mut list = list![1, 5];
+        list.splice_at(1, list![2, 3, 4]);
+        assert_eq!(list, list![1, 2, 3, 4, 5]);
+
+        list.splice_at(0, list![-1, 0]);
+        assert_eq!(list, list![-1, 0, 1, 2, 3, 4, 5]);
+
+        list.splice_at(7, list![6]);
+        list.splice_at(100, list![7]);
+        list.splice_at(3, LinkedList::new());
+        assert_eq!(list, list![-1, 0, 1, 2, 3, 4, 5, 6, 7]);
+        list.push_back(8);
+        assert_eq!(list.len(), 10);
+
+        let mut list = LinkedList::new();
+        list.splice_at(0, list![1, 2]);
+        assert_eq!(list, list![1, 2]);
+
+        // returns right away instead of walking past the end
+        list.splice_at(usize::MAX, list![3]);
+        assert_eq!(list, list![1, 2, 3]);
+        assert_eq!(list.len(), 3);
+    }
+
+    #[test]
+    fn split_at_is_inclusive() {
+        // element at index goes to the first list
+        let (first, second) = list![1, 2, 3].split_at(1).unwrap();
+        assert_eq!(first, list![1, 2]);
+        assert_eq!(second, list![3]);
+
+        // so splitting at the last index leaves the second list empty,
+        // and the whole list can never end up in the second one
+        let (first, second) = list![1, 2, 3].split_at(2).unwrap();
+        assert_eq!(first, list![1, 2, 3]);
+        assert!(second.is_empty());
+
+        // split_off(index + 1) gives the same result
+        let mut list = list![1, 2, 3];
+        let second = list.split_off(2);
+        assert_eq!(list, list![1, 2]);
+        assert_eq!(second, list![3]);
+    }
+
+    #[test]
+    fn split_off() {
+        let mut list = list![1, 2, 3, 4];
+        let second = list.split_off(1);
+        assert_eq!(list, list![1]);
+        assert_eq!(second, list![2, 3, 4]);
+
+        let mut list = list![1, 2, 3];
+        let second = list.split_off(3);
+        assert_eq!(list, list![1, 2, 3]);
+        assert!(second.is_empty());
+        list.push_back(4);
+        assert_eq!(list.len(), 4);
+
+        let mut list = list![1, 2, 3];
+        let mut second = list.split_off(0);
+        assert!(list.is_empty());
+        second.push_back(4);
+        assert_eq!(second, list![1, 2, 3, 4]);
+    }
+
+    #[test]
+    #[should_panic(expected = "cannot split off at index 4, list length is 3")]
+    fn split_off_out_of_bounds() {
+        let mut list = list![1, 2, 3];
+        list.split_off(4);
+    }
+
+    #[test]
+    fn drain() {
+        let mut list = list![1, 2, 3, 4, 5];
+        assert_eq!(list.drain(1..3).collect::<Vec<_>>(), vec![2, 3]);
+        assert_eq!(list, list![1, 4, 5]);
+
+        assert_eq!(list.drain(1..=2).collect::<Vec<_>>(), vec![4, 5]);
+        assert_eq!(list, list![1]);
+        list.push_back(2);
+        assert_eq!(list, list![1, 2]);
+
+        assert_eq!(list.drain(1..1).count(), 0);
+        assert_eq!(list.drain(..).collect::<Vec<_>>(), vec![1, 2]);
+        assert!(list.is_empty());
+
+        let mut list = list![1, 2, 3, 4, 5];
+        assert_eq!(list.drain(3..).collect::<Vec<_>>(), vec![4, 5]);
+        assert_eq!(list.drain(..1).collect::<Vec<_>>(), vec![1]);
+        assert_eq!(list, list![2, 3]);
+
+        // range is removed even if iterator is not consumed
+        let mut list = list![1, 2, 3, 4];
+        assert_eq!(list.drain(1..3).next(), Some(2));
+        assert_eq!(list, list![1, 4]);
+    }
+
+    #[test]
+    #[should_panic(expected = "drain range end 4 is out of bounds for list of length 3")]
+    fn drain_out_of_bounds() {
+        let mut list = list![1, 2, 3];
+        list.drain(1..4);
+    }
+
+    #[test]
+    fn iter_len_and_size_hint() {
+        let mut list = list![1, 2, 3];
+        let mut iter = list.iter();
+        assert_eq!(iter.len(), 3);
+        iter.next();
+        assert_eq!(iter.size_hint(), (2, Some(2)));
+        iter.next();
+        iter.next();
+        assert_eq!(iter.len(), 0);
+        assert_eq!(iter.next(), None);
+        assert_eq!(iter.next(), None);
+
+        let mut iter = list.iter_mut();
+        iter.next();
+        assert_eq!(iter.len(), 2);
+
+        let mut iter = list.into_iter();
+        assert_eq!(iter.len(), 3);
+        iter.next();
+        assert_eq!(iter.size_hint(), (2, Some(2)));
+    }
+
+    #[test]
+    fn iter_nth_and_last() {
+        let mut list = list![1, 2, 3, 4, 5];
+
+        let mut iter = list.iter();
+        assert_eq!(iter.nth(1), Some(&2));
+        assert_eq!(iter.len(), 3);
+        assert_eq!(iter.next(), Some(&3));
+        assert_eq!(iter.nth(5), None);
+        assert_eq!(iter.next(), None);
+        assert_eq!(list.iter().last(), Some(&5));
+        assert_eq!(list.iter().skip(5).last(), None);
+
+        let mut iter = list.iter_mut();
+        *iter.nth(2).unwrap() = 30;
+        assert_eq!(iter.len(), 2);
+        *iter.last().unwrap() = 50;
+        assert_eq!(list, list![1, 2, 30, 4, 50]);
+
+        let mut iter = list.clone().into_iter();
+        assert_eq!(iter.nth(3), Some(4));
+        assert_eq!(iter.nth(1), None);
+        assert_eq!(iter.next(), None);
+        assert_eq!(list.into_iter().last(), Some(50));
+        assert_eq!(LinkedList::<i32>::new().into_iter().last(), None);
+    }
+
+    #[test]
+    fn to_dot() {
+        let dot = list![1, 2, 3].to_dot();
+        assert!(dot.contains("p0 [label=\"head\", shape=plaintext];\n    p0 -> n0;"));
+        assert!(dot.contains("p1 [label=\"tail\", shape=plaintext];\n    p1 -> n2;"));
+        assert!(dot.contains("n1 -> n2;"));
+        assert!(!LinkedList::<i32>::new().to_dot().contains("->"));
+    }
+
+    #[test]
+    fn iterators_and_cursors_are_send_and_sync() {
+        fn assert_send_sync<S: Send + Sync>(_: S) {}
+        fn assert_send<S: Send>(_: S) {}
+
+        let mut list = list![1, 2];
+        assert_send_sync(list.iter());
+        assert_send_sync(list.iter_mut());
+        assert_send_sync(list.cursor_front());
+        assert_send_sync(list.cursor_front_mut());
+
+        // `&mut Cell` can be sent to another thread, `&Cell` can't
+        let mut list = list![std::cell::Cell::new(1)];
+        assert_send(list.iter_mut());
+        assert_send(list.cursor_front_mut());
+    }
 }
@@ -1,29 +1,117 @@
-use std::rc::Rc;
+use std::{collections::HashMap, fmt, iter::FusedIterator, ops::Deref, ptr, rc::Rc, sync::Arc};
 
-pub struct LinkedList<T> {
-    head: Option<Rc<Node<T>>>,
+use crate::{display, dot::Dot, sequence::PersistentSequence, ListError};
+
+/// Kind of reference counted pointer that a list uses to share its nodes between versions.
+/// `RcPointer` is cheaper, `ArcPointer` lets versions of a list be sent to other threads
+pub trait PointerKind {
+    type Pointer<U>: Deref<Target = U> + Clone;
+
+    fn new<U>(data: U) -> Self::Pointer<U>;
+
+    /// Returns the pointee if this is the only pointer to it, otherwise drops the pointer.
+    /// When several pointers are dropped at the same time on different threads, exactly one
+    /// of them gets the pointee
+    fn into_inner<U>(pointer: Self::Pointer<U>) -> Option<U>;
+
+    fn strong_count<U>(pointer: &Self::Pointer<U>) -> usize;
+
+    /// Returns a mutable reference to the pointee, cloning it first if it's shared
+    fn make_mut<U: Clone>(pointer: &mut Self::Pointer<U>) -> &mut U;
+}
+
+pub struct RcPointer;
+
+impl PointerKind for RcPointer {
+    type Pointer<U> = Rc<U>;
+
+    fn new<U>(data: U) -> Rc<U> {
+        Rc::new(data)
+    }
+
+    fn into_inner<U>(pointer: Rc<U>) -> Option<U> {
+        Rc::into_inner(pointer)
+    }
+
+    fn strong_count<U>(pointer: &Rc<U>) -> usize {
+        Rc::strong_count(pointer)
+    }
+
+    fn make_mut<U: Clone>(pointer: &mut Rc<U>) -> &mut U {
+        Rc::make_mut(pointer)
+    }
 }
 
-pub struct Node<T> {
+pub struct ArcPointer;
+
+impl PointerKind for ArcPointer {
+    type Pointer<U> = Arc<U>;
+
+    fn new<U>(data: U) -> Arc<U> {
+        Arc::new(data)
+    }
+
+    fn into_inner<U>(pointer: Arc<U>) -> Option<U> {
+        Arc::into_inner(pointer)
+    }
+
+    fn strong_count<U>(pointer: &Arc<U>) -> usize {
+        Arc::strong_count(pointer)
+    }
+
+    fn make_mut<U: Clone>(pointer: &mut Arc<U>) -> &mut U {
+        Arc::make_mut(pointer)
+    }
+}
+
+/// Persistent list, generic over the pointer used to share nodes.
+/// Use `LinkedList` or `ArcLinkedList` instead of naming this type directly
+pub struct GenericLinkedList<T, P: PointerKind> {
+    head: Option<P::Pointer<Node<T, P>>>,
+}
+
+pub type LinkedList<T> = GenericLinkedList<T, RcPointer>;
+
+/// Same as `LinkedList`, but `Send + Sync` when `T` is, so versions can be shared across threads
+pub type ArcLinkedList<T> = GenericLinkedList<T, ArcPointer>;
+
+pub struct Node<T, P: PointerKind = RcPointer> {
     data: T,
-    next: Option<Rc<Node<T>>>,
+    next: Option<P::Pointer<Node<T, P>>>,
 }
 
-impl<T> Node<T> {
+impl<T, P: PointerKind> Node<T, P> {
     pub fn new(data: T) -> Self {
         Self { data, next: None }
     }
 }
 
-impl<T> Default for LinkedList<T> {
+// Copies the element, but the copy still points to the same next node
+impl<T: Clone, P: PointerKind> Clone for Node<T, P> {
+    fn clone(&self) -> Self {
+        Self {
+            data: self.data.clone(),
+            next: self.next.clone(),
+        }
+    }
+}
+
+impl<T, P: PointerKind> Default for GenericLinkedList<T, P> {
     fn default() -> Self {
-        LinkedList { head: None }
+        GenericLinkedList { head: None }
     }
 }
 
-impl<T> LinkedList<T> {
-    pub fn new(head: Option<Rc<Node<T>>>) -> Self {
-        LinkedList { head }
+// Cloning a list only clones the pointer to its head, all nodes are shared
+impl<T, P: PointerKind> Clone for GenericLinkedList<T, P> {
+    fn clone(&self) -> Self {
+        Self::new(self.head.clone())
+    }
+}
+
+impl<T, P: PointerKind> GenericLinkedList<T, P> {
+    pub fn new(head: Option<P::Pointer<Node<T, P>>>) -> Self {
+        GenericLinkedList { head }
     }
 
     pub fn is_empty(&self) -> bool {
@@ -42,41 +130,352 @@ impl<T> LinkedList<T> {
     //               |
     // list3 -> X ---+
 
-    pub fn tail(&self) -> LinkedList<T> {
+    pub fn tail(&self) -> Self {
         if self.is_empty() {
-            return LinkedList::default();
+            return Self::default();
         }
 
-        LinkedList::new(self.head.as_ref().and_then(|n| n.next.clone()))
+        Self::new(self.head.as_ref().and_then(|n| n.next.clone()))
     }
 
-    pub fn push_front(&self, data: T) -> LinkedList<T> {
+    pub fn push_front(&self, data: T) -> Self {
         if self.is_empty() {
-            return LinkedList::new(Some(Rc::new(Node::new(data))));
+            return Self::new(Some(P::new(Node::new(data))));
         }
 
         let mut node = Node::new(data);
         node.next = self.head.clone();
 
-        LinkedList::new(Some(Rc::new(node)))
+        Self::new(Some(P::new(node)))
     }
 
     pub fn peek(&self) -> Option<&T> {
         self.head.as_ref().map(|n| &n.data)
     }
 
-    pub fn iter(&self) -> LinkedListIterator<T> {
+    pub fn iter(&self) -> LinkedListIterator<'_, T, P> {
         LinkedListIterator {
             current: self.head.as_deref(),
         }
     }
+
+    /// O(n), the length isn't stored since it would differ between versions sharing nodes
+    pub fn len(&self) -> usize {
+        let mut len = 0;
+        let mut current = self.head.as_deref();
+        while let Some(node) = current {
+            len += 1;
+            current = node.next.as_deref();
+        }
+
+        len
+    }
+
+    pub fn nth(&self, n: usize) -> Option<&T> {
+        self.iter().nth(n)
+    }
+
+    pub fn fold<B, F: FnMut(B, &T) -> B>(&self, init: B, f: F) -> B {
+        self.iter().fold(init, f)
+    }
+
+    /// Returns the list without its first `n` elements. Like `tail`, the result
+    /// shares its nodes with `self`
+    pub fn drop(&self, n: usize) -> Self {
+        Self::new(self.skip_nodes(n).cloned())
+    }
+
+    pub fn map<U, F: FnMut(&T) -> U>(&self, f: F) -> GenericLinkedList<U, P> {
+        let mapped: Vec<U> = self.iter().map(f).collect();
+        GenericLinkedList::prepend(mapped, GenericLinkedList::default())
+    }
+
+    /// Returns the index in `self` of the first node that is also part of `other`,
+    /// or `None` if the lists don't share any nodes. Nodes are compared by address, not value
+    pub fn shares_tail_with(&self, other: &Self) -> Option<usize> {
+        let (len, other_len) = (self.len(), other.len());
+        let skipped = len.saturating_sub(other_len);
+
+        // shared nodes always form a common suffix, so after lining up the ends
+        // both lists reach the first shared node at the same step
+        let mut current = self.skip_nodes(skipped);
+        let mut other = other.skip_nodes(other_len.saturating_sub(len));
+        let mut index = skipped;
+        while let (Some(a), Some(b)) = (current, other) {
+            if ptr::eq(&**a, &**b) {
+                return Some(index);
+            }
+            current = a.next.as_ref();
+            other = b.next.as_ref();
+            index += 1;
+        }
+
+        None
+    }
+
+    /// Reports how many nodes the given versions of a list share with each other
+    pub fn sharing_report(versions: &[&Self]) -> SharingReport {
+        // how many of the versions go through each node
+        let mut owners: HashMap<*const Node<T, P>, usize> = HashMap::new();
+        for version in versions {
+            let mut current = version.head.as_deref();
+            while let Some(node) = current {
+                *owners.entry(node).or_default() += 1;
+                current = node.next.as_deref();
+            }
+        }
+
+        let versions = versions.iter().map(|version| {
+            let mut shared_suffix_len = 0;
+            let mut current = version.head.as_deref();
+            while let Some(node) = current {
+                if owners[&(node as *const _)] > 1 {
+                    shared_suffix_len += 1;
+                }
+                current = node.next.as_deref();
+            }
+
+            VersionSharing {
+                len: version.len(),
+                shared_suffix_len,
+                head_strong_count: version.head.as_ref().map(P::strong_count),
+            }
+        });
+
+        SharingReport {
+            versions: versions.collect(),
+            unique_nodes: owners.len(),
+        }
+    }
+
+    fn skip_nodes(&self, n: usize) -> Option<&P::Pointer<Node<T, P>>> {
+        let mut current = self.head.as_ref();
+        for _ in 0..n {
+            current = current.and_then(|node| node.next.as_ref());
+        }
+
+        current
+    }
+
+    // pushes `items` in front of `list`, keeping their order
+    fn prepend<I>(items: I, list: Self) -> Self
+    where
+        I: IntoIterator<Item = T>,
+        I::IntoIter: DoubleEndedIterator,
+    {
+        items
+            .into_iter()
+            .rev()
+            .fold(list, |list, data| list.push_front(data))
+    }
+}
+
+// Operations that build new nodes have to clone the elements out of the shared ones
+impl<T: Clone, P: PointerKind> GenericLinkedList<T, P> {
+    /// Returns a mutable reference to the first element. The head node is copied
+    /// first if other lists share it, otherwise it's edited in place
+    pub fn peek_mut(&mut self) -> Option<&mut T> {
+        self.get_mut(0)
+    }
+
+    /// Returns a mutable reference to the element at `index`. Every node up to it that
+    /// is shared with another list gets copied, so other lists never see the change.
+    /// Nodes after `index` stay shared
+    pub fn get_mut(&mut self, index: usize) -> Option<&mut T> {
+        // check the node exists first, so an out of bounds index doesn't copy anything
+        self.skip_nodes(index)?;
+
+        let mut current = self.head.as_mut();
+        for _ in 0..index {
+            current = current.and_then(|node| P::make_mut(node).next.as_mut());
+        }
+
+        current.map(|node| &mut P::make_mut(node).data)
+    }
+
+    /// Returns a new list with `f` applied to the element at `index`. Only the nodes
+    /// up to `index` are copied, the rest is shared with `self`
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index` is out of bounds
+    pub fn update<F: FnOnce(&mut T)>(&self, index: usize, f: F) -> Self {
+        let mut list = self.clone();
+        match list.get_mut(index) {
+            Some(data) => f(data),
+            None => panic!(
+                "{}",
+                ListError::IndexOutOfBounds {
+                    index,
+                    len: self.len()
+                }
+            ),
+        }
+
+        list
+    }
+
+    /// Returns `self` followed by `other`. Only the nodes of `self` are copied,
+    /// the result shares all of `other`'s nodes
+    pub fn append(&self, other: &Self) -> Self {
+        let items: Vec<&T> = self.iter().collect();
+        Self::prepend(items.into_iter().cloned(), other.clone())
+    }
+
+    pub fn reverse(&self) -> Self {
+        self.fold(Self::default(), |list, data| list.push_front(data.clone()))
+    }
+
+    /// Keeps the elements for which `f` returns true. Everything after the last removed
+    /// element is kept as is, so that part is shared with `self` instead of copied
+    pub fn filter<F: FnMut(&T) -> bool>(&self, mut f: F) -> Self {
+        let keep: Vec<bool> = self.iter().map(&mut f).collect();
+        let shared_from = keep.iter().rposition(|keep| !keep).map_or(0, |i| i + 1);
+
+        let items: Vec<T> = self
+            .iter()
+            .zip(keep)
+            .take(shared_from)
+            .filter(|(_, keep)| *keep)
+            .map(|(data, _)| data.clone())
+            .collect();
+        Self::prepend(items, self.drop(shared_from))
+    }
+
+    /// Returns the first `n` elements. They have to be copied since the
+    /// last one gets a different `next`, unless the whole list is taken
+    pub fn take(&self, n: usize) -> Self {
+        if self.drop(n).is_empty() {
+            return self.clone();
+        }
+
+        let items: Vec<&T> = self.iter().take(n).collect();
+        Self::prepend(items.into_iter().cloned(), Self::default())
+    }
+}
+
+impl<T: fmt::Debug, P: PointerKind> fmt::Debug for GenericLinkedList<T, P> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
+
+impl<T: fmt::Display, P: PointerKind> fmt::Display for GenericLinkedList<T, P> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        display::arrows(self.iter(), f)
+    }
+}
+
+impl<T: fmt::Debug, P: PointerKind> GenericLinkedList<T, P> {
+    /// Same as `versions_to_dot` with a single version called `head`
+    pub fn to_dot(&self) -> String {
+        Self::versions_to_dot(&[("head", self)])
+    }
+
+    /// Renders several versions in one graph, each pointing at its first node under
+    /// the given name. Nodes shared between versions are drawn once, so the graph
+    /// shows the same picture as the comment on `tail`
+    pub fn versions_to_dot(versions: &[(&str, &Self)]) -> String {
+        let mut dot = Dot::new();
+        let mut ids: HashMap<*const Node<T, P>, usize> = HashMap::new();
+
+        for (name, version) in versions {
+            let mut prev = None;
+            let mut current = version.head.as_deref();
+            while let Some(node) = current {
+                let seen = ids.get(&(node as *const _)).copied();
+                let id = seen.unwrap_or(ids.len());
+                match prev {
+                    Some(prev) => dot.edge(prev, id),
+                    None => dot.pointer(name, Some(id)),
+                }
+                // the rest of the list was already drawn for an earlier version
+                if seen.is_some() {
+                    break;
+                }
+
+                ids.insert(node, id);
+                dot.node(id, &node.data);
+                prev = Some(id);
+                current = node.next.as_deref();
+            }
+
+            if version.is_empty() {
+                dot.pointer(name, None);
+            }
+        }
+
+        dot.finish()
+    }
+}
+
+impl<T, P: PointerKind> PersistentSequence<T> for GenericLinkedList<T, P> {
+    type Iter<'a>
+        = LinkedListIterator<'a, T, P>
+    where
+        T: 'a,
+        P: 'a;
+
+    fn len(&self) -> usize {
+        GenericLinkedList::len(self)
+    }
+
+    fn push_front(&self, data: T) -> Self {
+        GenericLinkedList::push_front(self, data)
+    }
+
+    fn tail(&self) -> Self {
+        GenericLinkedList::tail(self)
+    }
+
+    fn front(&self) -> Option<&T> {
+        self.peek()
+    }
+
+    fn iter(&self) -> LinkedListIterator<'_, T, P> {
+        GenericLinkedList::iter(self)
+    }
+}
+
+/// Result of `GenericLinkedList::sharing_report`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SharingReport {
+    /// One entry per version, in the order they were passed in
+    pub versions: Vec<VersionSharing>,
+    /// Number of distinct nodes across all versions, i.e. how many are actually allocated
+    pub unique_nodes: usize,
 }
 
-pub struct LinkedListIterator<'a, T> {
-    current: Option<&'a Node<T>>,
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct VersionSharing {
+    pub len: usize,
+    /// How many nodes at the end of this version are also used by some other version
+    pub shared_suffix_len: usize,
+    /// `strong_count` of the first node, `None` for an empty list. This counts every
+    /// owner of the node, including lists that weren't passed to the report
+    pub head_strong_count: Option<usize>,
 }
 
-impl<'a, T> Iterator for LinkedListIterator<'a, T> {
+// Default drop would recurse once per node through `Option<Rc<Node<T>>>`, which overflows
+// the stack on long lists. We unlink nodes one by one, but only as long as we are
+// the last owner; as soon as a node is shared with another list, that list keeps it alive.
+// `into_inner` rather than `Rc::try_unwrap`, otherwise two threads dropping the last two
+// owners of a node at once could both see it as shared, and the node would then be
+// freed through the default recursive drop
+impl<T, P: PointerKind> Drop for GenericLinkedList<T, P> {
+    fn drop(&mut self) {
+        let mut current = self.head.take();
+        while let Some(mut node) = current.and_then(P::into_inner) {
+            current = node.next.take();
+        }
+    }
+}
+
+pub struct LinkedListIterator<'a, T, P: PointerKind = RcPointer> {
+    current: Option<&'a Node<T, P>>,
+}
+
+impl<'a, T, P: PointerKind> Iterator for LinkedListIterator<'a, T, P> {
     type Item = &'a T;
     fn next(&mut self) -> Option<Self::Item> {
         self.current.map(|n| {
@@ -84,6 +483,46 @@ impl<'a, T> Iterator for LinkedListIterator<'a, T> {
             &n.data
         })
     }
+
+    // lists don't store their length (it would have to be shared between versions),
+    // so the remaining nodes are counted on demand
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let mut len = 0;
+        let mut current = self.current;
+        while let Some(n) = current {
+            len += 1;
+            current = n.next.as_deref();
+        }
+        (len, Some(len))
+    }
+
+    fn nth(&mut self, n: usize) -> Option<Self::Item> {
+        for _ in 0..n {
+            self.current = self.current?.next.as_deref();
+        }
+        self.next()
+    }
+
+    fn last(self) -> Option<Self::Item> {
+        let mut current = self.current?;
+        while let Some(next) = current.next.as_deref() {
+            current = next;
+        }
+        Some(&current.data)
+    }
+}
+
+impl<T, P: PointerKind> ExactSizeIterator for LinkedListIterator<'_, T, P> {}
+
+impl<T, P: PointerKind> FusedIterator for LinkedListIterator<'_, T, P> {}
+
+// Builds a list with the same elements as the slice, for tests here and in `zipper.rs`
+#[cfg(test)]
+pub(crate) fn from_slice(items: &[i32]) -> LinkedList<i32> {
+    items
+        .iter()
+        .rev()
+        .fold(LinkedList::default(), |list, &i| list.push_front(i))
 }
 
 #[cfg(test)]
@@ -103,6 +542,33 @@ mod test {
         assert_eq!(iter.next(), None);
     }
 
+    #[test]
+    fn iter_len() {
+        let list = LinkedList::default()
+            .push_front(1)
+            .push_front(2)
+            .push_front(3);
+        let mut iter = list.iter();
+        assert_eq!(iter.len(), 3);
+        iter.next();
+        assert_eq!(iter.size_hint(), (2, Some(2)));
+        assert_eq!(list.tail().iter().len(), 2);
+        assert_eq!(LinkedList::<i32>::default().iter().len(), 0);
+    }
+
+    #[test]
+    fn iter_nth_and_last() {
+        let list = from_slice(&[1, 2, 3, 4, 5]);
+        let mut iter = list.iter();
+        assert_eq!(iter.nth(1), Some(&2));
+        assert_eq!(iter.len(), 3);
+        assert_eq!(iter.next(), Some(&3));
+        assert_eq!(iter.nth(2), None);
+        assert_eq!(iter.next(), None);
+        assert_eq!(list.iter().last(), Some(&5));
+        assert_eq!(LinkedList::<i32>::default().iter().last(), None);
+    }
+
     #[test]
     fn tail() {
         let list = LinkedList::default();
@@ -136,4 +602,338 @@ mod test {
         let list = list.tail();
         assert_eq!(list.peek(), None);
     }
+
+    fn same_head<T>(a: &LinkedList<T>, b: &LinkedList<T>) -> bool {
+        match (&a.head, &b.head) {
+            (Some(a), Some(b)) => Rc::ptr_eq(a, b),
+            (None, None) => true,
+            _ => false,
+        }
+    }
+
+    #[test]
+    fn len_nth_and_fold() {
+        let list = from_slice(&[1, 2, 3]);
+        assert_eq!(list.len(), 3);
+        assert_eq!(LinkedList::<i32>::default().len(), 0);
+        assert_eq!(list.nth(0), Some(&1));
+        assert_eq!(list.nth(2), Some(&3));
+        assert_eq!(list.nth(3), None);
+        assert_eq!(list.fold(0, |acc, x| acc * 10 + x), 123);
+    }
+
+    #[test]
+    fn append() {
+        let a = from_slice(&[1, 2]);
+        let b = from_slice(&[3, 4]);
+        let list = a.append(&b);
+        assert!(list.iter().eq([1, 2, 3, 4].iter()));
+        assert!(same_head(&list.drop(2), &b));
+
+        // originals are untouched
+        assert!(a.iter().eq([1, 2].iter()));
+        assert!(b.iter().eq([3, 4].iter()));
+
+        assert!(same_head(&LinkedList::default().append(&b), &b));
+        assert!(a.append(&LinkedList::default()).iter().eq([1, 2].iter()));
+    }
+
+    #[test]
+    fn reverse_and_map() {
+        let list = from_slice(&[1, 2, 3]);
+        assert!(list.reverse().iter().eq([3, 2, 1].iter()));
+        assert!(list.iter().eq([1, 2, 3].iter()));
+
+        let mut calls = Vec::new();
+        let strings = list.map(|x| {
+            calls.push(*x);
+            x.to_string()
+        });
+        assert!(strings.iter().eq(["1", "2", "3"].iter()));
+        assert_eq!(calls, [1, 2, 3]);
+        assert!(LinkedList::<i32>::default().reverse().is_empty());
+    }
+
+    #[test]
+    fn filter() {
+        let list = from_slice(&[1, 2, 3, 4, 6, 8]);
+        let even = list.filter(|x| x % 2 == 0);
+        assert!(even.iter().eq([2, 4, 6, 8].iter()));
+        // everything after the last odd number is shared
+        assert!(same_head(&even.drop(1), &list.drop(3)));
+        assert!(list.iter().eq([1, 2, 3, 4, 6, 8].iter()));
+
+        assert!(same_head(&list.filter(|_| true), &list));
+        assert!(list.filter(|_| false).is_empty());
+    }
+
+    #[test]
+    fn take_and_drop() {
+        let list = from_slice(&[1, 2, 3, 4]);
+        assert!(list.take(2).iter().eq([1, 2].iter()));
+        assert!(list.take(0).is_empty());
+        assert!(same_head(&list.take(4), &list));
+        assert!(same_head(&list.take(10), &list));
+
+        let rest = list.drop(2);
+        assert!(rest.iter().eq([3, 4].iter()));
+        assert!(same_head(&rest, &list.tail().tail()));
+        assert!(list.drop(10).is_empty());
+        assert!(list.iter().eq([1, 2, 3, 4].iter()));
+    }
+
+    #[test]
+    fn shares_tail_with() {
+        let base = from_slice(&[3, 4, 5]);
+        let a = base.push_front(2).push_front(1);
+        let b = base.push_front(9);
+        assert_eq!(a.shares_tail_with(&b), Some(2));
+        assert_eq!(b.shares_tail_with(&a), Some(1));
+        assert_eq!(a.shares_tail_with(&a.tail()), Some(1));
+        assert_eq!(a.shares_tail_with(&a), Some(0));
+
+        // equal values in separate nodes don't count
+        let copy = from_slice(&[1, 2, 3, 4, 5]);
+        assert_eq!(a.shares_tail_with(&copy), None);
+        assert_eq!(a.shares_tail_with(&LinkedList::default()), None);
+    }
+
+    #[test]
+    fn sharing_report() {
+        let base = from_slice(&[3, 4]);
+        let a = base.push_front(2).push_front(1);
+        let b = base.push_front(9);
+        let other = from_slice(&[7]);
+
+        let report = LinkedList::sharing_report(&[&a, &b, &other, &LinkedList::default()]);
+        assert_eq!(report.unique_nodes, 6);
+        let shared: Vec<_> = report
+            .versions
+            .iter()
+            .map(|v| v.shared_suffix_len)
+            .collect();
+        assert_eq!(shared, [2, 2, 0, 0]);
+        let lens: Vec<_> = report.versions.iter().map(|v| v.len).collect();
+        assert_eq!(lens, [4, 3, 1, 0]);
+
+        // the head of `b` is only owned by `b`, while `base` is also the next of two nodes
+        assert_eq!(report.versions[1].head_strong_count, Some(1));
+        assert_eq!(report.versions[3].head_strong_count, None);
+        let report = LinkedList::sharing_report(&[&base]);
+        assert_eq!(report.versions[0].head_strong_count, Some(3));
+        assert_eq!(report.versions[0].shared_suffix_len, 0);
+    }
+
+    #[test]
+    fn update() {
+        let list = from_slice(&[1, 2, 3, 4]);
+        let updated = list.update(1, |x| *x *= 10);
+        assert!(updated.iter().eq([1, 20, 3, 4].iter()));
+        assert!(list.iter().eq([1, 2, 3, 4].iter()));
+        assert_eq!(updated.shares_tail_with(&list), Some(2));
+
+        let last = list.update(3, |x| *x = 0);
+        assert!(last.iter().eq([1, 2, 3, 0].iter()));
+        assert_eq!(last.shares_tail_with(&list), None);
+    }
+
+    #[test]
+    #[should_panic(expected = "index 4 is out of bounds for list of length 4")]
+    fn update_out_of_bounds() {
+        from_slice(&[1, 2, 3, 4]).update(4, |x| *x = 0);
+    }
+
+    #[test]
+    fn get_mut_edits_unique_nodes_in_place() {
+        let mut list = from_slice(&[1, 2, 3]);
+        let head = Rc::as_ptr(list.head.as_ref().unwrap());
+        *list.peek_mut().unwrap() = 10;
+        *list.get_mut(2).unwrap() = 30;
+        assert!(list.iter().eq([10, 2, 30].iter()));
+        // nothing else owned the nodes, so nothing was copied
+        assert_eq!(Rc::as_ptr(list.head.as_ref().unwrap()), head);
+        assert_eq!(list.get_mut(3), None);
+        assert_eq!(LinkedList::<i32>::default().peek_mut(), None);
+    }
+
+    #[test]
+    fn get_mut_out_of_bounds_copies_nothing() {
+        let base = from_slice(&[1, 2, 3]);
+        let mut copy = base.clone();
+        assert_eq!(copy.get_mut(100), None);
+        assert_eq!(copy.get_mut(3), None);
+        assert_eq!(copy.shares_tail_with(&base), Some(0));
+        assert_eq!(LinkedList::sharing_report(&[&base, &copy]).unique_nodes, 3);
+    }
+
+    #[test]
+    fn get_mut_copies_shared_prefix() {
+        let shared = from_slice(&[3, 4]);
+        let mut list = shared.push_front(2).push_front(1);
+        let other = list.clone();
+
+        *list.get_mut(1).unwrap() = 20;
+        assert!(list.iter().eq([1, 20, 3, 4].iter()));
+        assert!(other.iter().eq([1, 2, 3, 4].iter()));
+        assert!(same_head(&list.drop(2), &shared));
+
+        // the copied prefix is now owned by `list` alone, so it's edited in place
+        let head = Rc::as_ptr(list.head.as_ref().unwrap());
+        *list.peek_mut().unwrap() = 10;
+        assert_eq!(Rc::as_ptr(list.head.as_ref().unwrap()), head);
+
+        // editing the shared suffix copies just the nodes up to it
+        *list.get_mut(2).unwrap() = 30;
+        assert!(list.iter().eq([10, 20, 30, 4].iter()));
+        assert!(shared.iter().eq([3, 4].iter()));
+        assert!(same_head(&list.drop(3), &shared.tail()));
+    }
+
+    #[test]
+    fn versions_to_dot() {
+        // list1 = A -> B -> C, list2 = tail(list1), list3 = push(list2, X)
+        let list1 = LinkedList::default()
+            .push_front('C')
+            .push_front('B')
+            .push_front('A');
+        let list2 = list1.tail();
+        let list3 = list2.push_front('X');
+        let empty = LinkedList::default();
+
+        let dot = LinkedList::versions_to_dot(&[
+            ("list1", &list1),
+            ("list2", &list2),
+            ("list3", &list3),
+            ("empty", &empty),
+        ]);
+        assert_eq!(
+            dot,
+            r#"digraph list {
+    rankdir=LR;
+    node [shape=box];
+    p0 [label="list1", shape=plaintext];
+    p0 -> n0;
+    n0 [label="'A'"];
+    n0 -> n1;
+    n1 [label="'B'"];
+    n1 -> n2;
+    n2 [label="'C'"];
+    p1 [label="list2", shape=plaintext];
+    p1 -> n1;
+    p2 [label="list3", shape=plaintext];
+    p2 -> n3;
+    n3 [label="'X'"];
+    n3 -> n1;
+    p3 [label="empty", shape=plaintext];
+}
+"#
+        );
+        assert_eq!(list3.to_dot().matches(" -> ").count(), 3);
+    }
+
+    #[test]
+    fn debug_and_display() {
+        let list = LinkedList::default().push_front(2).push_front(1);
+        assert_eq!(format!("{:?}", list), "[1, 2]");
+        assert_eq!(format!("{:#?}", list.tail()), "[\n    2,\n]");
+        assert_eq!(list.to_string(), "1 -> 2 -> ∅");
+        assert_eq!(format!("{:03}", list), "001 -> 002 -> ∅");
+        assert_eq!(LinkedList::<i32>::default().to_string(), "∅");
+    }
+
+    #[test]
+    fn drop_long_list() {
+        let mut list = LinkedList::default();
+        for i in 0..2_000_000 {
+            list = list.push_front(i);
+        }
+        drop(list);
+    }
+
+    #[test]
+    fn drop_long_list_with_shared_tail() {
+        let mut shared = LinkedList::default();
+        for i in 0..2_000_000 {
+            shared = shared.push_front(i);
+        }
+
+        let first = shared.push_front(1);
+        let second = shared.push_front(2);
+        drop(shared);
+
+        drop(first);
+        assert_eq!(second.peek(), Some(&2));
+        assert_eq!(second.tail().peek(), Some(&1_999_999));
+        assert_eq!(second.iter().count(), 2_000_001);
+        drop(second);
+    }
+
+    #[test]
+    fn arc_list_is_send_and_sync() {
+        fn assert_send_sync<S: Send + Sync>() {}
+        assert_send_sync::<ArcLinkedList<i32>>();
+        assert_send_sync::<ArcLinkedList<String>>();
+    }
+
+    #[test]
+    fn arc_list_shared_between_threads() {
+        let list = ArcLinkedList::default()
+            .push_front(1)
+            .push_front(2)
+            .push_front(3);
+
+        let handles: Vec<_> = (0..4)
+            .map(|i| {
+                let list = list.clone();
+                std::thread::spawn(move || {
+                    // every thread builds its own version on top of the shared nodes
+                    let list = list.tail().push_front(10 + i);
+                    assert_eq!(list.peek(), Some(&(10 + i)));
+                    list.iter().sum::<i32>()
+                })
+            })
+            .collect();
+
+        for (i, handle) in handles.into_iter().enumerate() {
+            assert_eq!(handle.join().unwrap(), 10 + i as i32 + 2 + 1);
+        }
+
+        // original version is untouched
+        assert!(list.iter().eq([3, 2, 1].iter()));
+    }
+
+    #[test]
+    fn drop_long_arc_list() {
+        let mut list = ArcLinkedList::default();
+        for i in 0..2_000_000 {
+            list = list.push_front(i);
+        }
+        drop(list);
+    }
+
+    #[test]
+    fn drop_long_arc_list_on_many_threads_at_once() {
+        let mut list = ArcLinkedList::default();
+        for i in 0..1_000_000 {
+            list = list.push_front(i);
+        }
+
+        let barrier = std::sync::Arc::new(std::sync::Barrier::new(4));
+        let handles: Vec<_> = (0..4)
+            .map(|i| {
+                let list = list.push_front(i);
+                let barrier = barrier.clone();
+                std::thread::spawn(move || {
+                    barrier.wait();
+                    drop(list);
+                })
+            })
+            .collect();
+
+        // the shared tail is now only owned by the threads, which race to drop it
+        drop(list);
+        for handle in handles {
+            handle.join().unwrap();
+        }
+    }
 }
@@ -0,0 +1,755 @@
+use std::{fmt, iter::FusedIterator, marker::PhantomData, ptr::NonNull};
+
+use crate::{dot::Dot, sequence::Sequence, ListError};
+
+type Link<T> = Option<NonNull<Node<T>>>;
+
+// Doubly linked list: we keep raw pointers to both ends, so that pushing and popping
+// at either end doesn't have to walk the list like `linked_list_2` does.
+// Every node is allocated with `Box::new` and owned by the list; `prev` and `next`
+// are non-owning pointers, and a node is freed only when it is unlinked from the list.
+pub struct LinkedList<T> {
+    head: Link<T>,
+    tail: Link<T>,
+    len: usize,
+    // tells the compiler we own `Node<T>` values, even though we only store pointers
+    _marker: PhantomData<Box<Node<T>>>,
+}
+
+struct Node<T> {
+    data: T,
+    prev: Link<T>,
+    next: Link<T>,
+}
+
+// SAFETY: the list owns its nodes exclusively, same as `Box<Node<T>>` would
+unsafe impl<T: Send> Send for LinkedList<T> {}
+unsafe impl<T: Sync> Sync for LinkedList<T> {}
+
+impl<T> Default for LinkedList<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T> LinkedList<T> {
+    pub fn new() -> Self {
+        LinkedList {
+            head: None,
+            tail: None,
+            len: 0,
+            _marker: PhantomData,
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.head.is_none()
+    }
+
+    pub fn has_exactly_one_element(&self) -> bool {
+        self.len == 1
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn push_back(&mut self, data: T) {
+        let node = Node::new_raw(data, self.tail, None);
+        match self.tail {
+            // SAFETY: tail points to a live node owned by this list
+            Some(tail) => unsafe { (*tail.as_ptr()).next = Some(node) },
+            None => self.head = Some(node),
+        }
+        self.tail = Some(node);
+        self.len += 1;
+    }
+
+    pub fn push_front(&mut self, data: T) {
+        let node = Node::new_raw(data, None, self.head);
+        match self.head {
+            // SAFETY: head points to a live node owned by this list
+            Some(head) => unsafe { (*head.as_ptr()).prev = Some(node) },
+            None => self.tail = Some(node),
+        }
+        self.head = Some(node);
+        self.len += 1;
+    }
+
+    pub fn pop_back(&mut self) -> Option<T> {
+        self.tail.map(|tail| {
+            // SAFETY: tail was created by `Node::new_raw` and we are unlinking it right now,
+            // so nothing else will reach it afterwards
+            let node = unsafe { Box::from_raw(tail.as_ptr()) };
+            self.tail = node.prev;
+            match self.tail {
+                // SAFETY: prev points to a live node owned by this list
+                Some(new_tail) => unsafe { (*new_tail.as_ptr()).next = None },
+                None => self.head = None,
+            }
+            self.len -= 1;
+            node.data
+        })
+    }
+
+    pub fn pop_front(&mut self) -> Option<T> {
+        self.head.map(|head| {
+            // SAFETY: head was created by `Node::new_raw` and we are unlinking it right now,
+            // so nothing else will reach it afterwards
+            let node = unsafe { Box::from_raw(head.as_ptr()) };
+            self.head = node.next;
+            match self.head {
+                // SAFETY: next points to a live node owned by this list
+                Some(new_head) => unsafe { (*new_head.as_ptr()).prev = None },
+                None => self.tail = None,
+            }
+            self.len -= 1;
+            node.data
+        })
+    }
+
+    pub fn first_index_of(&self, data: T) -> Option<usize>
+    where
+        T: PartialEq,
+    {
+        self.iter().position(|d| *d == data)
+    }
+
+    /// Insert data at index, which is 0 based, meaning first element has index 0
+    /// If index >= list.len(), inserts at the end of the list
+    pub fn insert_at(&mut self, index: usize, data: T) {
+        if index == 0 {
+            self.push_front(data);
+            return;
+        }
+
+        if index >= self.len {
+            self.push_back(data);
+            return;
+        }
+
+        // new node goes between `index - 1` and `index`, both of which exist
+        let next = self.node_at(index).expect("index < len, so node exists");
+        // SAFETY: next and its prev are live nodes owned by this list
+        unsafe {
+            let prev = (*next.as_ptr()).prev.expect("index > 0, so prev exists");
+            let node = Node::new_raw(data, Some(prev), Some(next));
+            (*prev.as_ptr()).next = Some(node);
+            (*next.as_ptr()).prev = Some(node);
+        }
+        self.len += 1;
+    }
+
+    /// Splits list so that first list contains elements 0..=index and second one the rest.
+    /// Returns an error if list is empty or index >= list.len()
+    pub fn split_at(mut self, index: usize) -> Result<(LinkedList<T>, LinkedList<T>), ListError> {
+        if self.is_empty() {
+            return Err(ListError::Empty);
+        }
+
+        if index >= self.len {
+            return Err(ListError::IndexOutOfBounds {
+                index,
+                len: self.len,
+            });
+        }
+
+        let split_node = self.node_at(index).expect("index < len, so node exists");
+        // SAFETY: split_node is a live node owned by this list
+        let second_head = unsafe { (*split_node.as_ptr()).next.take() };
+
+        let mut second = Self::new();
+        if let Some(second_head) = second_head {
+            // SAFETY: second_head is a live node; from now on it belongs to `second`
+            unsafe { (*second_head.as_ptr()).prev = None };
+            second.head = Some(second_head);
+            second.tail = self.tail;
+            second.len = self.len - index - 1;
+        }
+
+        self.tail = Some(split_node);
+        self.len = index + 1;
+
+        Ok((self, second))
+    }
+
+    pub fn merge(&mut self, mut other: Self) {
+        let (Some(other_head), Some(other_tail)) = (other.head.take(), other.tail.take()) else {
+            return;
+        };
+
+        match self.tail {
+            // SAFETY: both are live nodes; other's nodes are now owned by self
+            Some(tail) => unsafe {
+                (*tail.as_ptr()).next = Some(other_head);
+                (*other_head.as_ptr()).prev = Some(tail);
+            },
+            None => self.head = Some(other_head),
+        }
+
+        self.tail = Some(other_tail);
+        self.len += other.len;
+        other.len = 0;
+    }
+
+    pub fn iter(&self) -> LinkedListIter<'_, T> {
+        LinkedListIter {
+            head: self.head,
+            tail: self.tail,
+            len: self.len,
+            _marker: PhantomData,
+        }
+    }
+
+    pub fn iter_mut(&mut self) -> LinkedListIterMut<'_, T> {
+        LinkedListIterMut {
+            head: self.head,
+            tail: self.tail,
+            len: self.len,
+            _marker: PhantomData,
+        }
+    }
+
+    // walks from whichever end is closer to index
+    fn node_at(&self, index: usize) -> Link<T> {
+        if index >= self.len {
+            return None;
+        }
+
+        // SAFETY: we only follow links between live nodes owned by this list
+        unsafe {
+            if index < self.len / 2 {
+                let mut current = self.head?;
+                for _ in 0..index {
+                    current = (*current.as_ptr()).next?;
+                }
+                Some(current)
+            } else {
+                let mut current = self.tail?;
+                for _ in index + 1..self.len {
+                    current = (*current.as_ptr()).prev?;
+                }
+                Some(current)
+            }
+        }
+    }
+}
+
+impl<T: fmt::Debug> LinkedList<T> {
+    /// `prev` links are drawn dashed
+    pub fn to_dot(&self) -> String {
+        let mut dot = Dot::new();
+        let len = dot.chain(self.iter(), true);
+        for i in 1..len {
+            dot.dashed_edge(i, i - 1);
+        }
+
+        dot.finish()
+    }
+}
+
+impl<T> Drop for LinkedList<T> {
+    fn drop(&mut self) {
+        while self.pop_front().is_some() {}
+    }
+}
+
+impl<T> IntoIterator for LinkedList<T> {
+    type Item = T;
+    type IntoIter = LinkedListIntoIter<T>;
+    fn into_iter(self) -> Self::IntoIter {
+        LinkedListIntoIter(self)
+    }
+}
+
+impl<T> Sequence<T> for LinkedList<T> {
+    type Iter<'a>
+        = LinkedListIter<'a, T>
+    where
+        T: 'a;
+
+    fn len(&self) -> usize {
+        LinkedList::len(self)
+    }
+
+    fn push_front(&mut self, data: T) {
+        LinkedList::push_front(self, data)
+    }
+
+    fn push_back(&mut self, data: T) {
+        LinkedList::push_back(self, data)
+    }
+
+    fn pop_front(&mut self) -> Option<T> {
+        LinkedList::pop_front(self)
+    }
+
+    fn pop_back(&mut self) -> Option<T> {
+        LinkedList::pop_back(self)
+    }
+
+    fn iter(&self) -> LinkedListIter<'_, T> {
+        LinkedList::iter(self)
+    }
+}
+
+pub struct LinkedListIntoIter<T>(LinkedList<T>);
+
+impl<T> Iterator for LinkedListIntoIter<T> {
+    type Item = T;
+    fn next(&mut self) -> Option<Self::Item> {
+        self.0.pop_front()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.0.len, Some(self.0.len))
+    }
+
+    fn last(mut self) -> Option<Self::Item> {
+        self.0.pop_back()
+    }
+}
+
+impl<T> DoubleEndedIterator for LinkedListIntoIter<T> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.0.pop_back()
+    }
+}
+
+impl<T> ExactSizeIterator for LinkedListIntoIter<T> {}
+
+impl<T> FusedIterator for LinkedListIntoIter<T> {}
+
+// `len` counts elements between `head` and `tail` that were not yet yielded,
+// so that front and back iteration stop when they meet in the middle
+pub struct LinkedListIter<'a, T> {
+    head: Link<T>,
+    tail: Link<T>,
+    len: usize,
+    _marker: PhantomData<&'a Node<T>>,
+}
+
+impl<'a, T> Iterator for LinkedListIter<'a, T> {
+    type Item = &'a T;
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.len == 0 {
+            return None;
+        }
+
+        self.head.map(|n| {
+            // SAFETY: the list is borrowed for 'a, so its nodes stay alive and unchanged
+            let node = unsafe { &*n.as_ptr() };
+            self.len -= 1;
+            self.head = node.next;
+            &node.data
+        })
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.len, Some(self.len))
+    }
+
+    fn nth(&mut self, n: usize) -> Option<Self::Item> {
+        if n >= self.len {
+            self.len = 0;
+            return None;
+        }
+        for _ in 0..n {
+            // SAFETY: same as in `next`, and there are more than n nodes left
+            self.head = self.head.and_then(|n| unsafe { (*n.as_ptr()).next });
+        }
+        self.len -= n;
+        self.next()
+    }
+
+    fn last(mut self) -> Option<Self::Item> {
+        self.next_back()
+    }
+}
+
+impl<'a, T> DoubleEndedIterator for LinkedListIter<'a, T> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.len == 0 {
+            return None;
+        }
+
+        self.tail.map(|n| {
+            // SAFETY: the list is borrowed for 'a, so its nodes stay alive and unchanged
+            let node = unsafe { &*n.as_ptr() };
+            self.len -= 1;
+            self.tail = node.prev;
+            &node.data
+        })
+    }
+}
+
+// SAFETY: the iterator only hands out `&T`, same as `&LinkedList<T>` would
+unsafe impl<T: Sync> Send for LinkedListIter<'_, T> {}
+unsafe impl<T: Sync> Sync for LinkedListIter<'_, T> {}
+
+impl<T> ExactSizeIterator for LinkedListIter<'_, T> {}
+
+impl<T> FusedIterator for LinkedListIter<'_, T> {}
+
+pub struct LinkedListIterMut<'a, T> {
+    head: Link<T>,
+    tail: Link<T>,
+    len: usize,
+    _marker: PhantomData<&'a mut Node<T>>,
+}
+
+// SAFETY: the iterator hands out `&mut T`, same as `&mut LinkedList<T>` would
+unsafe impl<T: Send> Send for LinkedListIterMut<'_, T> {}
+unsafe impl<T: Sync> Sync for LinkedListIterMut<'_, T> {}
+
+impl<'a, T> Iterator for LinkedListIterMut<'a, T> {
+    type Item = &'a mut T;
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.len == 0 {
+            return None;
+        }
+
+        self.head.map(|n| {
+            // SAFETY: the list is mutably borrowed for 'a, and every node is yielded
+            // at most once, so the returned references never alias
+            let node = unsafe { &mut *n.as_ptr() };
+            self.len -= 1;
+            self.head = node.next;
+            &mut node.data
+        })
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.len, Some(self.len))
+    }
+
+    fn nth(&mut self, n: usize) -> Option<Self::Item> {
+        if n >= self.len {
+            self.len = 0;
+            return None;
+        }
+        for _ in 0..n {
+            // SAFETY: only reads the links of nodes that haven't been yielded yet
+            self.head = self.head.and_then(|n| unsafe { (*n.as_ptr()).next });
+        }
+        self.len -= n;
+        self.next()
+    }
+
+    fn last(mut self) -> Option<Self::Item> {
+        self.next_back()
+    }
+}
+
+impl<'a, T> DoubleEndedIterator for LinkedListIterMut<'a, T> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.len == 0 {
+            return None;
+        }
+
+        self.tail.map(|n| {
+            // SAFETY: the list is mutably borrowed for 'a, and every node is yielded
+            // at most once, so the returned references never alias
+            let node = unsafe { &mut *n.as_ptr() };
+            self.len -= 1;
+            self.tail = node.prev;
+            &mut node.data
+        })
+    }
+}
+
+impl<T> ExactSizeIterator for LinkedListIterMut<'_, T> {}
+
+impl<T> FusedIterator for LinkedListIterMut<'_, T> {}
+
+impl<T> Node<T> {
+    fn new_raw(data: T, prev: Link<T>, next: Link<T>) -> NonNull<Node<T>> {
+        let node = Box::new(Node { data, prev, next });
+        // SAFETY: pointer returned by `Box::into_raw` is never null
+        unsafe { NonNull::new_unchecked(Box::into_raw(node)) }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn push_back() {
+        let mut list = LinkedList::new();
+        assert_eq!(list.len(), 0);
+        list.push_back(1);
+        assert_eq!(list.len(), 1);
+        list.push_back(2);
+        assert_eq!(list.len(), 2);
+    }
+
+    #[test]
+    fn push_front() {
+        let mut list = LinkedList::new();
+        assert_eq!(list.len(), 0);
+        list.push_front(1);
+        assert_eq!(list.len(), 1);
+        list.push_front(2);
+        assert_eq!(list.len(), 2);
+    }
+
+    #[test]
+    fn pop_back() {
+        let mut list = LinkedList::new();
+        assert_eq!(list.pop_back(), None);
+        list.push_back(1);
+        list.push_back(2);
+        assert_eq!(list.pop_back(), Some(2));
+        assert_eq!(list.pop_back(), Some(1));
+        assert_eq!(list.pop_back(), None);
+        assert!(list.is_empty());
+    }
+
+    #[test]
+    fn pop_front() {
+        let mut list = LinkedList::new();
+        assert_eq!(list.pop_front(), None);
+        list.push_back(1);
+        list.push_back(2);
+        assert_eq!(list.pop_front(), Some(1));
+        assert_eq!(list.pop_front(), Some(2));
+        assert_eq!(list.pop_front(), None);
+        assert!(list.is_empty());
+    }
+
+    #[test]
+    fn pop_front_and_back() {
+        let mut list = LinkedList::new();
+        list.push_back(2);
+        list.push_front(1);
+        list.push_back(3);
+        list.push_back(4);
+
+        assert_eq!(list.pop_front(), Some(1));
+        assert_eq!(list.pop_back(), Some(4));
+        assert_eq!(list.pop_front(), Some(2));
+        assert_eq!(list.pop_back(), Some(3));
+        assert_eq!(list.pop_back(), None);
+        assert_eq!(list.pop_front(), None);
+        assert_eq!(list.len(), 0);
+
+        // list must still be usable after being emptied from both ends
+        list.push_front(5);
+        assert_eq!(list.pop_back(), Some(5));
+    }
+
+    #[test]
+    fn has_one_element() {
+        let mut list = LinkedList::new();
+        assert!(!list.has_exactly_one_element());
+        list.push_back(1);
+        assert!(list.has_exactly_one_element());
+        list.push_back(2);
+        assert!(!list.has_exactly_one_element());
+        list.pop_back();
+        assert!(list.has_exactly_one_element());
+    }
+
+    #[test]
+    fn index_of() {
+        let mut list = LinkedList::new();
+        assert_eq!(list.first_index_of(1), None);
+        list.push_back(1);
+        list.push_back(2);
+        list.push_back(3);
+        assert_eq!(list.first_index_of(1), Some(0));
+        assert_eq!(list.first_index_of(2), Some(1));
+        assert_eq!(list.first_index_of(3), Some(2));
+        assert_eq!(list.first_index_of(4), None);
+    }
+
+    #[test]
+    fn insert_at() {
+        let mut list = LinkedList::new();
+        list.insert_at(0, 1);
+        list.insert_at(1, 2);
+        list.insert_at(2, 3);
+        list.insert_at(1, 4);
+        list.insert_at(3, 5);
+        list.insert_at(10, 6);
+        assert_eq!(list.len(), 6);
+        assert!(list.iter().eq([1, 4, 2, 5, 3, 6].iter()));
+        assert!(list.iter().rev().eq([6, 3, 5, 2, 4, 1].iter()));
+    }
+
+    #[test]
+    fn split_at() {
+        let mut list = LinkedList::new();
+        list.push_back(1);
+        list.push_back(2);
+        list.push_back(3);
+        list.push_back(4);
+        let (mut first, mut second) = list.split_at(1).unwrap();
+        assert_eq!(first.len(), 2);
+        assert_eq!(second.len(), 2);
+        assert_eq!(first.pop_back(), Some(2));
+        assert_eq!(second.pop_front(), Some(3));
+        assert_eq!(second.pop_back(), Some(4));
+        assert!(second.is_empty());
+
+        let mut list = LinkedList::new();
+        list.push_back(1);
+        list.push_back(2);
+        let (first, second) = list.split_at(1).unwrap();
+        assert_eq!(first.len(), 2);
+        assert_eq!(second.len(), 0);
+        assert_eq!(
+            first.split_at(2).err(),
+            Some(ListError::IndexOutOfBounds { index: 2, len: 2 })
+        );
+        assert_eq!(
+            LinkedList::<i32>::new().split_at(0).err(),
+            Some(ListError::Empty)
+        );
+    }
+
+    #[test]
+    fn merge() {
+        let mut list = LinkedList::new();
+        list.push_back(1);
+        list.push_back(2);
+        let mut list2 = LinkedList::new();
+        list2.push_back(3);
+        list2.push_back(4);
+        list.merge(list2);
+        assert_eq!(list.len(), 4);
+        assert!(list.iter().eq([1, 2, 3, 4].iter()));
+        assert!(list.iter().rev().eq([4, 3, 2, 1].iter()));
+
+        list.merge(LinkedList::new());
+        assert_eq!(list.len(), 4);
+
+        let mut empty = LinkedList::new();
+        empty.merge(list);
+        assert_eq!(empty.len(), 4);
+        assert_eq!(empty.pop_back(), Some(4));
+    }
+
+    #[test]
+    fn into_iter() {
+        let mut list = LinkedList::new();
+        list.push_back(1);
+        list.push_back(2);
+        list.push_back(3);
+        let mut iter = list.into_iter();
+        assert_eq!(iter.next(), Some(1));
+        assert_eq!(iter.next_back(), Some(3));
+        assert_eq!(iter.next(), Some(2));
+        assert_eq!(iter.next(), None);
+        assert_eq!(iter.next_back(), None);
+    }
+
+    #[test]
+    fn iter() {
+        let mut list = LinkedList::new();
+        list.push_back(1);
+        list.push_back(2);
+        list.push_back(3);
+        let mut iter = list.iter();
+        assert_eq!(iter.next(), Some(&1));
+        assert_eq!(iter.next_back(), Some(&3));
+        assert_eq!(iter.next(), Some(&2));
+        assert_eq!(iter.next_back(), None);
+        assert_eq!(iter.next(), None);
+    }
+
+    #[test]
+    fn iter_mut() {
+        let mut list = LinkedList::new();
+        list.push_back(1);
+        list.push_back(2);
+        list.push_back(3);
+        let mut iter = list.iter_mut();
+        assert_eq!(iter.next(), Some(&mut 1));
+        *iter.next_back().unwrap() = 30;
+        *iter.next().unwrap() = 20;
+        assert_eq!(iter.next(), None);
+        assert_eq!(iter.next_back(), None);
+
+        assert!(list.iter().eq([1, 20, 30].iter()));
+    }
+
+    #[test]
+    fn to_dot() {
+        let mut list = LinkedList::new();
+        list.push_back(1);
+        list.push_back(2);
+        let dot = list.to_dot();
+        assert!(dot.contains("n0 -> n1;"));
+        assert!(dot.contains("n1 -> n0 [style=dashed];"));
+        assert!(dot.contains("p1 -> n1;"));
+    }
+
+    #[test]
+    fn drop_long_list() {
+        let mut list = LinkedList::new();
+        for i in 0..2_000_000 {
+            list.push_back(i);
+        }
+        drop(list);
+    }
+
+    #[test]
+    fn iter_len() {
+        let mut list = LinkedList::new();
+        list.push_back(1);
+        list.push_back(2);
+        list.push_back(3);
+        let mut iter = list.iter();
+        iter.next();
+        iter.next_back();
+        assert_eq!(iter.len(), 1);
+        assert_eq!(list.iter_mut().rev().len(), 3);
+        assert_eq!(list.into_iter().skip(1).len(), 2);
+    }
+
+    #[test]
+    fn iter_nth_and_last() {
+        let mut list = LinkedList::new();
+        for i in 1..=5 {
+            list.push_back(i);
+        }
+
+        let mut iter = list.iter();
+        assert_eq!(iter.nth(1), Some(&2));
+        assert_eq!(iter.len(), 3);
+        assert_eq!(iter.next_back(), Some(&5));
+        assert_eq!(iter.nth(1), Some(&4));
+        assert_eq!(iter.next(), None);
+        assert_eq!(list.iter().last(), Some(&5));
+        assert_eq!(list.iter().skip(5).last(), None);
+
+        let mut iter = list.iter_mut();
+        *iter.nth(2).unwrap() = 30;
+        *iter.last().unwrap() = 50;
+        assert!(list.iter().eq([1, 2, 30, 4, 50].iter()));
+
+        // the last element was already yielded from the back
+        let mut iter = list.iter();
+        iter.next_back();
+        assert_eq!(iter.last(), Some(&4));
+        assert_eq!(list.into_iter().last(), Some(50));
+    }
+
+    #[test]
+    fn iterators_are_send_and_sync() {
+        fn assert_send_sync<S: Send + Sync>(_: S) {}
+        fn assert_send<S: Send>(_: S) {}
+
+        let mut list = LinkedList::new();
+        list.push_back(1);
+        assert_send_sync(list.iter());
+        assert_send_sync(list.iter_mut());
+
+        // `&mut Cell` can be sent to another thread, `&Cell` can't
+        let mut list = LinkedList::new();
+        list.push_back(std::cell::Cell::new(1));
+        assert_send(list.iter_mut());
+    }
+}
@@ -0,0 +1,439 @@
+use crate::linked_list_3::LinkedList;
+
+// Persistent FIFO queue built from persistent stacks (Hood-Melville real-time queue, as in
+// Okasaki's "Purely Functional Data Structures" 8.2.1).
+// `front` holds elements in dequeue order, `rear` holds newly enqueued elements in reverse.
+//
+// queue = enqueue 1, 2, 3 then 4, 5
+// front -> 1 -> 2 -> 3
+// rear  -> 5 -> 4
+//
+// A batched queue reverses `rear` all at once when `front` runs out. That's amortised O(1)
+// only as long as versions are used linearly: dequeuing again and again from an old version
+// redoes the same O(n) reversal every time. Instead, as soon as `rear` gets longer than
+// `front`, we start building `front ++ reverse(rear)` in the background and do a couple
+// of its steps in every operation. The rotation is done before `front` runs out, so every
+// operation is O(1) in the worst case, no matter which versions it's applied to.
+pub struct PersistentQueue<T> {
+    front: LinkedList<T>,
+    // elements in front of the queue, including the ones still being rotated into `front`
+    front_len: usize,
+    rotation: Rotation<T>,
+    rear: LinkedList<T>,
+    rear_len: usize,
+}
+
+// State of the incremental `front ++ reverse(rear)`. It first reverses both lists
+// one element at a time, then pushes the reversed front onto the reversed rear.
+// `live` counts how many elements of the copied front are still in the queue, since
+// elements dequeued in the meantime must not be copied into the new front
+enum Rotation<T> {
+    Idle,
+    Reversing {
+        live: usize,
+        front: LinkedList<T>,
+        reversed_front: LinkedList<T>,
+        rear: LinkedList<T>,
+        reversed_rear: LinkedList<T>,
+    },
+    Appending {
+        live: usize,
+        reversed_front: LinkedList<T>,
+        result: LinkedList<T>,
+    },
+    Done(LinkedList<T>),
+}
+
+impl<T> Clone for Rotation<T> {
+    fn clone(&self) -> Self {
+        match self {
+            Rotation::Idle => Rotation::Idle,
+            Rotation::Reversing {
+                live,
+                front,
+                reversed_front,
+                rear,
+                reversed_rear,
+            } => Rotation::Reversing {
+                live: *live,
+                front: front.clone(),
+                reversed_front: reversed_front.clone(),
+                rear: rear.clone(),
+                reversed_rear: reversed_rear.clone(),
+            },
+            Rotation::Appending {
+                live,
+                reversed_front,
+                result,
+            } => Rotation::Appending {
+                live: *live,
+                reversed_front: reversed_front.clone(),
+                result: result.clone(),
+            },
+            Rotation::Done(result) => Rotation::Done(result.clone()),
+        }
+    }
+}
+
+impl<T: Clone> Rotation<T> {
+    fn step(&self) -> Rotation<T> {
+        match self {
+            Rotation::Reversing {
+                live,
+                front,
+                reversed_front,
+                rear,
+                reversed_rear,
+            } => {
+                // when a rotation starts, rear is one element longer than front
+                let y = rear.peek().expect("rear runs out after front").clone();
+                match front.peek() {
+                    Some(x) => Rotation::Reversing {
+                        live: live + 1,
+                        front: front.tail(),
+                        reversed_front: reversed_front.push_front(x.clone()),
+                        rear: rear.tail(),
+                        reversed_rear: reversed_rear.push_front(y),
+                    },
+                    None => Rotation::Appending {
+                        live: *live,
+                        reversed_front: reversed_front.clone(),
+                        result: reversed_rear.push_front(y),
+                    },
+                }
+            }
+            Rotation::Appending {
+                live: 0, result, ..
+            } => Rotation::Done(result.clone()),
+            Rotation::Appending {
+                live,
+                reversed_front,
+                result,
+            } => {
+                let x = reversed_front.peek().expect("live elements were reversed");
+                Rotation::Appending {
+                    live: live - 1,
+                    reversed_front: reversed_front.tail(),
+                    result: result.push_front(x.clone()),
+                }
+            }
+            Rotation::Idle | Rotation::Done(_) => self.clone(),
+        }
+    }
+
+    // one element of the front was dequeued, so it must not end up in the new front
+    fn invalidate(&self) -> Rotation<T> {
+        match self {
+            Rotation::Reversing {
+                live,
+                front,
+                reversed_front,
+                rear,
+                reversed_rear,
+            } => Rotation::Reversing {
+                live: live - 1,
+                front: front.clone(),
+                reversed_front: reversed_front.clone(),
+                rear: rear.clone(),
+                reversed_rear: reversed_rear.clone(),
+            },
+            // everything left of the old front was already copied, drop the first copy
+            Rotation::Appending {
+                live: 0, result, ..
+            } => Rotation::Done(result.tail()),
+            Rotation::Appending {
+                live,
+                reversed_front,
+                result,
+            } => Rotation::Appending {
+                live: live - 1,
+                reversed_front: reversed_front.clone(),
+                result: result.clone(),
+            },
+            Rotation::Idle | Rotation::Done(_) => self.clone(),
+        }
+    }
+}
+
+impl<T> Default for PersistentQueue<T> {
+    fn default() -> Self {
+        PersistentQueue {
+            front: LinkedList::default(),
+            front_len: 0,
+            rotation: Rotation::Idle,
+            rear: LinkedList::default(),
+            rear_len: 0,
+        }
+    }
+}
+
+// Cloning is O(1), both versions share all nodes
+impl<T> Clone for PersistentQueue<T> {
+    fn clone(&self) -> Self {
+        PersistentQueue {
+            front: self.front.clone(),
+            front_len: self.front_len,
+            rotation: self.rotation.clone(),
+            rear: self.rear.clone(),
+            rear_len: self.rear_len,
+        }
+    }
+}
+
+impl<T> PersistentQueue<T> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    pub fn len(&self) -> usize {
+        self.front_len + self.rear_len
+    }
+
+    /// Returns the element that would be dequeued next
+    pub fn peek(&self) -> Option<&T> {
+        self.front.peek()
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &T> {
+        // while rotating, the elements after `front` are the reversed old rear,
+        // which is split between the rotation's lists
+        let rotated: Vec<&T> = match &self.rotation {
+            Rotation::Reversing {
+                rear,
+                reversed_rear,
+                ..
+            } => {
+                let rear: Vec<&T> = rear.iter().collect();
+                rear.into_iter().rev().chain(reversed_rear.iter()).collect()
+            }
+            Rotation::Appending { result, .. } => {
+                let rotated_len = self.front_len - self.front.len();
+                result.iter().skip(result.len() - rotated_len).collect()
+            }
+            Rotation::Idle | Rotation::Done(_) => Vec::new(),
+        };
+
+        // rear is in reverse order, so we have to collect it to walk it backwards
+        let rear: Vec<&T> = self.rear.iter().collect();
+        self.front
+            .iter()
+            .chain(rotated)
+            .chain(rear.into_iter().rev())
+    }
+}
+
+impl<T: Clone> PersistentQueue<T> {
+    /// Returns a new queue with data added at the back, self is left untouched
+    pub fn enqueue(&self, data: T) -> PersistentQueue<T> {
+        Self::checked(
+            self.front.clone(),
+            self.front_len,
+            self.rotation.clone(),
+            self.rear.push_front(data),
+            self.rear_len + 1,
+        )
+    }
+
+    /// Returns a new queue without the front element, self is left untouched.
+    /// Dequeuing from an empty queue returns an empty queue, same as `LinkedList::tail`
+    pub fn dequeue(&self) -> PersistentQueue<T> {
+        if self.is_empty() {
+            return Self::default();
+        }
+
+        Self::checked(
+            self.front.tail(),
+            self.front_len - 1,
+            self.rotation.invalidate(),
+            self.rear.clone(),
+            self.rear_len,
+        )
+    }
+
+    // starts a rotation once rear gets longer than front, and moves the current one along
+    fn checked(
+        front: LinkedList<T>,
+        front_len: usize,
+        rotation: Rotation<T>,
+        rear: LinkedList<T>,
+        rear_len: usize,
+    ) -> PersistentQueue<T> {
+        if rear_len <= front_len {
+            return Self::stepped(front, front_len, rotation, rear, rear_len);
+        }
+
+        let rotation = Rotation::Reversing {
+            live: 0,
+            front: front.clone(),
+            reversed_front: LinkedList::default(),
+            rear,
+            reversed_rear: LinkedList::default(),
+        };
+        Self::stepped(
+            front,
+            front_len + rear_len,
+            rotation,
+            LinkedList::default(),
+            0,
+        )
+    }
+
+    // two steps per operation are enough to finish a rotation before front runs out
+    fn stepped(
+        front: LinkedList<T>,
+        front_len: usize,
+        rotation: Rotation<T>,
+        rear: LinkedList<T>,
+        rear_len: usize,
+    ) -> PersistentQueue<T> {
+        let (front, rotation) = match rotation.step().step() {
+            Rotation::Done(front) => (front, Rotation::Idle),
+            rotation => (front, rotation),
+        };
+
+        PersistentQueue {
+            front,
+            front_len,
+            rotation,
+            rear,
+            rear_len,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn enqueue_dequeue() {
+        let queue = PersistentQueue::new();
+        assert_eq!(queue.peek(), None);
+        assert!(queue.is_empty());
+
+        let queue = queue.enqueue(1).enqueue(2).enqueue(3);
+        assert_eq!(queue.len(), 3);
+        assert_eq!(queue.peek(), Some(&1));
+
+        let queue = queue.dequeue();
+        assert_eq!(queue.peek(), Some(&2));
+
+        let queue = queue.enqueue(4);
+        let queue = queue.dequeue();
+        assert_eq!(queue.peek(), Some(&3));
+        let queue = queue.dequeue();
+        assert_eq!(queue.peek(), Some(&4));
+        let queue = queue.dequeue();
+        assert_eq!(queue.peek(), None);
+        assert_eq!(queue.len(), 0);
+
+        // make sure dequeue on empty queue works
+        let queue = queue.dequeue();
+        assert!(queue.is_empty());
+    }
+
+    #[test]
+    fn iter() {
+        let queue = PersistentQueue::new().enqueue(1).enqueue(2).enqueue(3);
+        let queue = queue.dequeue().enqueue(4).enqueue(5);
+        assert!(queue.iter().eq([2, 3, 4, 5].iter()));
+    }
+
+    #[test]
+    fn old_versions_are_unaffected() {
+        let empty = PersistentQueue::new();
+        let v1 = empty.enqueue(1);
+        let v2 = v1.enqueue(2);
+        let v3 = v2.enqueue(3);
+        let v4 = v3.dequeue();
+        let v5 = v4.dequeue();
+        let v6 = v5.enqueue(4);
+        // branch off an old version
+        let branch = v2.enqueue(10).dequeue();
+
+        assert!(empty.is_empty());
+        assert!(v1.iter().eq([1].iter()));
+        assert!(v2.iter().eq([1, 2].iter()));
+        assert!(v3.iter().eq([1, 2, 3].iter()));
+        assert!(v4.iter().eq([2, 3].iter()));
+        assert!(v5.iter().eq([3].iter()));
+        assert!(v6.iter().eq([3, 4].iter()));
+        assert!(branch.iter().eq([2, 10].iter()));
+
+        assert_eq!(v3.peek(), Some(&1));
+        assert_eq!(v5.peek(), Some(&3));
+        assert_eq!(branch.peek(), Some(&2));
+        assert_eq!(v3.len(), 3);
+    }
+
+    #[test]
+    fn long_queue() {
+        let mut queue = PersistentQueue::new();
+        for i in 0..100_000 {
+            queue = queue.enqueue(i);
+        }
+        for i in 0..100_000 {
+            assert_eq!(queue.peek(), Some(&i));
+            queue = queue.dequeue();
+        }
+        assert!(queue.is_empty());
+    }
+
+    #[test]
+    fn dequeue_repeatedly_from_long_old_version() {
+        let mut queue = PersistentQueue::new();
+        for i in 0..100_000 {
+            queue = queue.enqueue(i);
+        }
+        let old = (0..50_000).fold(queue, |queue, _| queue.dequeue());
+
+        // a batched queue would redo an O(n) reversal on every one of these
+        for _ in 0..100_000 {
+            let next = old.dequeue();
+            assert_eq!(next.peek(), Some(&50_001));
+            assert_eq!(next.len(), 49_999);
+        }
+        assert_eq!(old.peek(), Some(&50_000));
+    }
+
+    #[test]
+    fn matches_vec_deque_across_versions() {
+        use std::collections::VecDeque;
+
+        // every step picks some version and either enqueues to it or dequeues from it
+        let mut versions = vec![(PersistentQueue::new(), VecDeque::new())];
+        let mut seed: u64 = 7;
+        // Miri is far too slow for the full run
+        let steps = if cfg!(miri) { 200 } else { 2_000 };
+        for i in 0..steps {
+            seed = seed
+                .wrapping_mul(6364136223846793005)
+                .wrapping_add(1442695040888963407);
+            // mostly recent versions, so the queue grows long enough to rotate a lot
+            let back = (seed >> 33) as usize % versions.len().min(8);
+            let (queue, model) = &versions[versions.len() - 1 - back];
+            let (queue, mut model) = (queue.clone(), model.clone());
+
+            let queue = if (seed >> 20).is_multiple_of(3) {
+                model.pop_front();
+                queue.dequeue()
+            } else {
+                model.push_back(i);
+                queue.enqueue(i)
+            };
+
+            assert_eq!(queue.len(), model.len());
+            assert_eq!(queue.peek(), model.front());
+            assert!(queue.iter().eq(model.iter()));
+            versions.push((queue, model));
+        }
+
+        for (queue, model) in &versions {
+            assert!(queue.iter().eq(model.iter()));
+        }
+    }
+}
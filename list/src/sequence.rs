@@ -0,0 +1,179 @@
+// Common interface of the list modules, so generic code doesn't have to care which list
+// it gets. The modules grew their APIs separately, so inherent method names differ
+// (`find` vs `first_index_of`, `val` vs `peek`), and the traits give them one set of names.
+//
+// `Sequence` is for lists that are edited in place: lists 1, 2 and 4.
+// `PersistentSequence` is for lists where every operation returns a new version and leaves
+// the old one valid, which only `linked_list_3` supports. It doesn't implement `Sequence`:
+// editing in place would have to copy every node it shares with other versions.
+
+/// A list that is edited in place
+pub trait Sequence<T>: Default {
+    type Iter<'a>: Iterator<Item = &'a T>
+    where
+        Self: 'a,
+        T: 'a;
+
+    fn len(&self) -> usize;
+
+    fn push_front(&mut self, data: T);
+
+    fn push_back(&mut self, data: T);
+
+    fn pop_front(&mut self) -> Option<T>;
+
+    fn pop_back(&mut self) -> Option<T>;
+
+    fn iter(&self) -> Self::Iter<'_>;
+
+    fn is_empty(&self) -> bool {
+        self.iter().next().is_none()
+    }
+
+    fn front(&self) -> Option<&T> {
+        self.iter().next()
+    }
+
+    /// Index of the first element equal to `data`
+    fn index_of(&self, data: &T) -> Option<usize>
+    where
+        T: PartialEq,
+    {
+        self.iter().position(|item| item == data)
+    }
+
+    fn contains(&self, data: &T) -> bool
+    where
+        T: PartialEq,
+    {
+        self.index_of(data).is_some()
+    }
+}
+
+/// A list where every operation returns a new version, leaving `self` untouched
+pub trait PersistentSequence<T>: Default + Clone {
+    type Iter<'a>: Iterator<Item = &'a T>
+    where
+        Self: 'a,
+        T: 'a;
+
+    fn len(&self) -> usize;
+
+    fn push_front(&self, data: T) -> Self;
+
+    /// Returns the list without its first element, or an empty list if `self` is empty
+    fn tail(&self) -> Self;
+
+    fn front(&self) -> Option<&T>;
+
+    fn iter(&self) -> Self::Iter<'_>;
+
+    fn is_empty(&self) -> bool {
+        self.front().is_none()
+    }
+
+    /// Index of the first element equal to `data`
+    fn index_of(&self, data: &T) -> Option<usize>
+    where
+        T: PartialEq,
+    {
+        self.iter().position(|item| item == data)
+    }
+
+    fn contains(&self, data: &T) -> bool
+    where
+        T: PartialEq,
+    {
+        self.index_of(data).is_some()
+    }
+}
+
+// Conformance suite: every implementation runs the same checks
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{linked_list_1, linked_list_2, linked_list_3, linked_list_4};
+
+    fn collect<T: Clone, L: Sequence<T>>(list: &L) -> Vec<T> {
+        list.iter().cloned().collect()
+    }
+
+    fn check_sequence<L: Sequence<i32>>() {
+        let mut list = L::default();
+        assert!(list.is_empty());
+        assert_eq!(list.len(), 0);
+        assert_eq!(list.front(), None);
+        assert_eq!(list.pop_front(), None);
+        assert_eq!(list.pop_back(), None);
+
+        list.push_back(2);
+        list.push_front(1);
+        list.push_back(3);
+        assert!(!list.is_empty());
+        assert_eq!(list.len(), 3);
+        assert_eq!(list.front(), Some(&1));
+        assert_eq!(collect(&list), [1, 2, 3]);
+
+        assert_eq!(list.index_of(&3), Some(2));
+        assert_eq!(list.index_of(&4), None);
+        assert!(list.contains(&2));
+
+        assert_eq!(list.pop_back(), Some(3));
+        assert_eq!(list.pop_front(), Some(1));
+        assert_eq!(list.len(), 1);
+        assert_eq!(list.pop_back(), Some(2));
+        assert!(list.is_empty());
+
+        // the list stays usable after being emptied
+        list.push_back(4);
+        assert_eq!(collect(&list), [4]);
+        assert_eq!(list.pop_front(), Some(4));
+        assert_eq!(list.pop_front(), None);
+    }
+
+    fn check_persistent_sequence<L: PersistentSequence<i32>>() {
+        let empty = L::default();
+        assert!(empty.is_empty());
+        assert_eq!(empty.len(), 0);
+        assert_eq!(empty.front(), None);
+        assert!(empty.tail().is_empty());
+
+        let one = empty.push_front(2);
+        let two = one.push_front(1);
+        assert_eq!(two.len(), 2);
+        assert_eq!(two.front(), Some(&1));
+        assert!(two.iter().eq([1, 2].iter()));
+        assert_eq!(two.index_of(&2), Some(1));
+        assert!(!two.contains(&3));
+
+        let other = two.tail().push_front(5);
+        assert!(other.iter().eq([5, 2].iter()));
+
+        // older versions are untouched
+        assert!(empty.is_empty());
+        assert!(one.iter().eq([2].iter()));
+        assert!(two.iter().eq([1, 2].iter()));
+        assert!(two.clone().iter().eq(two.iter()));
+    }
+
+    #[test]
+    fn linked_list_1() {
+        check_sequence::<linked_list_1::LinkedList<i32>>();
+    }
+
+    #[test]
+    fn linked_list_2() {
+        check_sequence::<linked_list_2::LinkedList<i32>>();
+    }
+
+    #[test]
+    fn linked_list_3() {
+        check_persistent_sequence::<linked_list_3::LinkedList<i32>>();
+        check_persistent_sequence::<linked_list_3::ArcLinkedList<i32>>();
+    }
+
+    #[test]
+    fn linked_list_4() {
+        check_sequence::<linked_list_4::LinkedList<i32>>();
+    }
+}
@@ -0,0 +1,171 @@
+// Serde support, enabled with the `serde` feature.
+//
+// Every list is written as a flat sequence of its elements, the same as a `Vec`. Deriving
+// the impls would nest one level per node instead, which overflows the stack on long lists
+// both when writing and when reading them back. Reading goes through a `Vec` first, since
+// lists 1 and 3 can only be built cheaply from the back.
+
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+use crate::{linked_list_1, linked_list_2, linked_list_3, linked_list_4};
+
+impl<T: Serialize> Serialize for linked_list_1::LinkedList<T> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_seq(self.iter())
+    }
+}
+
+impl<'de, T: Deserialize<'de>> Deserialize<'de> for linked_list_1::LinkedList<T> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let items = Vec::deserialize(deserializer)?;
+        let mut list = linked_list_1::LinkedList::new();
+        for item in items.into_iter().rev() {
+            list.push_front(item);
+        }
+
+        Ok(list)
+    }
+}
+
+impl<T: Serialize> Serialize for linked_list_2::LinkedList<T> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_seq(self)
+    }
+}
+
+impl<'de, T: Deserialize<'de>> Deserialize<'de> for linked_list_2::LinkedList<T> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        Vec::deserialize(deserializer).map(linked_list_2::LinkedList::from)
+    }
+}
+
+// Shared nodes are written out once per list, so versions that shared a tail
+// before serializing won't share it after deserializing
+impl<T: Serialize, P: linked_list_3::PointerKind> Serialize
+    for linked_list_3::GenericLinkedList<T, P>
+{
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_seq(self.iter())
+    }
+}
+
+impl<'de, T: Deserialize<'de>, P: linked_list_3::PointerKind> Deserialize<'de>
+    for linked_list_3::GenericLinkedList<T, P>
+{
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let items: Vec<T> = Vec::deserialize(deserializer)?;
+        Ok(items
+            .into_iter()
+            .rev()
+            .fold(Self::default(), |list, item| list.push_front(item)))
+    }
+}
+
+impl<T: Serialize> Serialize for linked_list_4::LinkedList<T> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_seq(self.iter())
+    }
+}
+
+impl<'de, T: Deserialize<'de>> Deserialize<'de> for linked_list_4::LinkedList<T> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let items = Vec::deserialize(deserializer)?;
+        let mut list = linked_list_4::LinkedList::new();
+        for item in items {
+            list.push_back(item);
+        }
+
+        Ok(list)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn json_round_trip<L: Serialize + for<'de> Deserialize<'de>>(list: &L) -> (String, L) {
+        let json = serde_json::to_string(list).unwrap();
+        let back = serde_json::from_str(&json).unwrap();
+        (json, back)
+    }
+
+    fn bincode_round_trip<L: Serialize + for<'de> Deserialize<'de>>(list: &L) -> L {
+        bincode::deserialize(&bincode::serialize(list).unwrap()).unwrap()
+    }
+
+    #[test]
+    fn linked_list_1() {
+        let mut list = linked_list_1::LinkedList::new();
+        list.push_back(String::from("a"));
+        list.push_back(String::from("b"));
+
+        let (json, back) = json_round_trip(&list);
+        assert_eq!(json, r#"["a","b"]"#);
+        assert_eq!(back, list);
+        assert_eq!(bincode_round_trip(&list), list);
+    }
+
+    #[test]
+    fn linked_list_2() {
+        let list = crate::list![1, 2, 3];
+
+        let (json, back) = json_round_trip(&list);
+        assert_eq!(json, "[1,2,3]");
+        assert_eq!(back, list);
+        assert_eq!(back.len(), 3);
+        assert_eq!(bincode_round_trip(&list), list);
+
+        let empty = linked_list_2::LinkedList::<i32>::new();
+        assert_eq!(json_round_trip(&empty).0, "[]");
+        assert!(bincode_round_trip(&empty).is_empty());
+    }
+
+    #[test]
+    fn linked_list_3() {
+        let list = linked_list_3::LinkedList::default()
+            .push_front(2)
+            .push_front(1);
+
+        let (json, back) = json_round_trip(&list);
+        assert_eq!(json, "[1,2]");
+        assert!(back.iter().eq(list.iter()));
+        assert!(bincode_round_trip(&list).iter().eq(list.iter()));
+
+        let arc: linked_list_3::ArcLinkedList<i32> = serde_json::from_str("[1,2]").unwrap();
+        assert!(arc.iter().eq([1, 2].iter()));
+    }
+
+    #[test]
+    fn linked_list_4() {
+        let mut list = linked_list_4::LinkedList::new();
+        list.push_back(1);
+        list.push_back(2);
+
+        let (json, back) = json_round_trip(&list);
+        assert_eq!(json, "[1,2]");
+        assert!(back.iter().eq(list.iter()));
+        assert!(bincode_round_trip(&list).iter().eq(list.iter()));
+    }
+
+    #[test]
+    fn long_lists() {
+        let list: linked_list_2::LinkedList<u32> = (0..1_000_000).collect();
+        let json = serde_json::to_string(&list).unwrap();
+        let back: linked_list_2::LinkedList<u32> = serde_json::from_str(&json).unwrap();
+        assert_eq!(back.len(), 1_000_000);
+
+        let list = (0..1_000_000)
+            .rev()
+            .fold(linked_list_3::LinkedList::default(), |list, i| {
+                list.push_front(i)
+            });
+        let back: linked_list_3::LinkedList<u32> = bincode_round_trip(&list);
+        assert!(back.iter().eq(list.iter()));
+    }
+
+    #[test]
+    fn wrong_shape() {
+        assert!(serde_json::from_str::<linked_list_2::LinkedList<i32>>(r#"{"a":1}"#).is_err());
+        assert!(serde_json::from_str::<linked_list_3::LinkedList<i32>>(r#"[1,"a"]"#).is_err());
+    }
+}
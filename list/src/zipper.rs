@@ -0,0 +1,203 @@
+use crate::linked_list_3::LinkedList;
+
+// Zipper over a persistent list, for editing around one position without walking from
+// the head every time. `left` holds the elements before the focus in reverse order,
+// `right` starts with the focused element.
+//
+// list   = 1 -> 2 -> 3 -> 4 -> 5, focus on 3
+// left  -> 2 -> 1
+// right -> 3 -> 4 -> 5
+//
+// Moving the focus moves one element from the head of one list to the head of the other,
+// and edits only touch the head of `right`, so every operation is O(1). Like the lists it's
+// made of, a zipper is never changed in place, and every operation returns a new version.
+// The focus may also sit past the last element (`right` is empty), where `insert` appends.
+pub struct Zipper<T> {
+    left: LinkedList<T>,
+    right: LinkedList<T>,
+    index: usize,
+}
+
+impl<T> Default for Zipper<T> {
+    fn default() -> Self {
+        Zipper {
+            left: LinkedList::default(),
+            right: LinkedList::default(),
+            index: 0,
+        }
+    }
+}
+
+// Cloning is O(1), both versions share all nodes
+impl<T> Clone for Zipper<T> {
+    fn clone(&self) -> Self {
+        Zipper {
+            left: self.left.clone(),
+            right: self.right.clone(),
+            index: self.index,
+        }
+    }
+}
+
+/// Focuses the first element of the list. The list's nodes are shared, not copied
+impl<T> From<LinkedList<T>> for Zipper<T> {
+    fn from(list: LinkedList<T>) -> Self {
+        Zipper {
+            left: LinkedList::default(),
+            right: list,
+            index: 0,
+        }
+    }
+}
+
+impl<T> Zipper<T> {
+    pub fn new(list: LinkedList<T>) -> Self {
+        Self::from(list)
+    }
+
+    /// Returns the focused element, or `None` if the focus is past the last element
+    pub fn focus(&self) -> Option<&T> {
+        self.right.peek()
+    }
+
+    /// Position of the focus in the list
+    pub fn index(&self) -> usize {
+        self.index
+    }
+
+    /// Returns a new zipper with the focused element removed, focusing the one after it.
+    /// Returns `None` if the focus is past the last element
+    pub fn delete(&self) -> Option<Self> {
+        self.focus()?;
+
+        Some(Zipper {
+            left: self.left.clone(),
+            right: self.right.tail(),
+            index: self.index,
+        })
+    }
+
+    /// Returns a new zipper with the focused element replaced by `data`.
+    /// Returns `None` if the focus is past the last element
+    pub fn replace(&self, data: T) -> Option<Self> {
+        self.focus()?;
+
+        Some(Zipper {
+            left: self.left.clone(),
+            right: self.right.tail().push_front(data),
+            index: self.index,
+        })
+    }
+
+    /// Returns a new zipper with `data` inserted before the focused element.
+    /// The new element becomes the focus
+    pub fn insert(&self, data: T) -> Self {
+        Zipper {
+            left: self.left.clone(),
+            right: self.right.push_front(data),
+            index: self.index,
+        }
+    }
+}
+
+impl<T: Clone> Zipper<T> {
+    /// Returns `None` if the focus is already on the first element
+    pub fn move_left(&self) -> Option<Self> {
+        let data = self.left.peek()?;
+
+        Some(Zipper {
+            left: self.left.tail(),
+            right: self.right.push_front(data.clone()),
+            index: self.index - 1,
+        })
+    }
+
+    /// Returns `None` if the focus is already past the last element
+    pub fn move_right(&self) -> Option<Self> {
+        let data = self.right.peek()?;
+
+        Some(Zipper {
+            left: self.left.push_front(data.clone()),
+            right: self.right.tail(),
+            index: self.index + 1,
+        })
+    }
+
+    /// Rebuilds the list. This is O(index), since the elements before the focus
+    /// have to be copied back in front of it; the rest of the list is shared
+    pub fn into_list(self) -> LinkedList<T> {
+        self.left.fold(self.right.clone(), |list, data| {
+            list.push_front(data.clone())
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::linked_list_3::from_slice;
+
+    #[test]
+    fn move_and_focus() {
+        let zipper = Zipper::new(from_slice(&[1, 2, 3]));
+        assert_eq!(zipper.focus(), Some(&1));
+        assert!(zipper.move_left().is_none());
+
+        let zipper = zipper.move_right().unwrap().move_right().unwrap();
+        assert_eq!(zipper.focus(), Some(&3));
+        assert_eq!(zipper.index(), 2);
+
+        let end = zipper.move_right().unwrap();
+        assert_eq!(end.focus(), None);
+        assert_eq!(end.index(), 3);
+        assert!(end.move_right().is_none());
+
+        let zipper = end.move_left().unwrap().move_left().unwrap();
+        assert_eq!(zipper.focus(), Some(&2));
+        assert_eq!(zipper.index(), 1);
+    }
+
+    #[test]
+    fn edits() {
+        let list = from_slice(&[1, 2, 3, 4]);
+        let zipper = Zipper::new(list.clone()).move_right().unwrap();
+
+        let replaced = zipper.replace(20).unwrap();
+        assert_eq!(replaced.focus(), Some(&20));
+
+        let inserted = replaced.insert(15);
+        assert_eq!(inserted.focus(), Some(&15));
+
+        let deleted = inserted
+            .move_right()
+            .unwrap()
+            .move_right()
+            .unwrap()
+            .delete()
+            .unwrap();
+        assert_eq!(deleted.focus(), Some(&4));
+
+        let result = deleted.into_list();
+        assert!(result.iter().eq([1, 15, 20, 4].iter()));
+        assert_eq!(result.shares_tail_with(&list), Some(3));
+
+        // every intermediate version is still valid
+        assert!(list.iter().eq([1, 2, 3, 4].iter()));
+        assert!(zipper.into_list().iter().eq([1, 2, 3, 4].iter()));
+        assert!(replaced.into_list().iter().eq([1, 20, 3, 4].iter()));
+        assert!(inserted.into_list().iter().eq([1, 15, 20, 3, 4].iter()));
+    }
+
+    #[test]
+    fn edits_past_the_end() {
+        let end = Zipper::new(from_slice(&[1])).move_right().unwrap();
+        assert!(end.replace(5).is_none());
+        assert!(end.delete().is_none());
+
+        let appended = end.insert(2).move_right().unwrap().insert(3);
+        assert!(appended.into_list().iter().eq([1, 2, 3].iter()));
+
+        let empty = Zipper::default().insert(1);
+        assert!(empty.into_list().iter().eq([1].iter()));
+    }
+}